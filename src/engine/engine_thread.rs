@@ -0,0 +1,174 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyRuntimeError;
+use std::cell::RefCell;
+use std::mem::ManuallyDrop;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Weak};
+use std::thread::{self, ThreadId};
+use crate::engine::v8engine::without_gil;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+thread_local! {
+    // 引擎线程上的句柄（弱引用），该线程上创建的 ThreadBound 据此把访问与释放转交回来
+    static CURRENT: RefCell<Weak<EngineThread>> = const { RefCell::new(Weak::new()) };
+}
+
+/// 持有 isolate 句柄、只能在所属引擎线程上访问与释放的类型。
+///
+/// # Safety
+///
+/// 实现者承诺：只要值仅在所属线程上被访问和析构，把它移动到其他线程存放是安全的，
+/// 即类型没有在移动本身时生效的线程相关状态（Rc、v8::Global 等满足这一点）
+pub unsafe trait EngineBound: 'static {}
+
+// 把 EngineBound 值在所属线程与其他线程之间移动的包装，只由 ThreadBound 与 EngineThread::spawn 使用
+struct ToOwner<T>(T);
+
+// SAFETY: 构造方保证值只在所属线程上被访问和释放，其他线程只负责传递（见 new）
+unsafe impl<T: EngineBound> Send for ToOwner<T> {}
+
+impl<T> ToOwner<T> {
+    // SAFETY: 调用方保证接收方线程是值的所属线程，或值在其他线程上只被存放而不被访问
+    unsafe fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    // 闭包通过方法取值，整体捕获包装而不是其中的字段
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+// 借用调用方栈上数据的任务。闭包先于结果通道析构：调用方收到结果或得知通道关闭时，
+// 闭包及其借用必然已经释放，因此可以把任务的生命周期擦除为 'static 交给引擎线程
+struct ScopedJob<F, R> {
+    f: Option<F>,
+    result: mpsc::SyncSender<R>,
+}
+
+impl<F: FnOnce() -> R, R> ScopedJob<F, R> {
+    fn run(mut self) {
+        // f 被调用后即被消耗；发生 panic 时其捕获值在展开中释放，之后才析构 self 关闭通道
+        let f = self.f.take().unwrap();
+        let _ = self.result.send(f());
+    }
+}
+
+impl<F, R> Drop for ScopedJob<F, R> {
+    fn drop(&mut self) {
+        // 未执行便被丢弃（引擎线程退出）时同样先释放闭包，result 字段随后析构
+        drop(self.f.take());
+    }
+}
+
+// 持有 isolate 的专用线程，按提交顺序逐个执行任务；最后一个句柄释放后线程退出
+pub struct EngineThread {
+    jobs: mpsc::Sender<Job>,
+    thread: ThreadId,
+}
+
+impl EngineThread {
+    // 启动引擎线程并在其中执行 init，返回绑定到该线程的值
+    pub fn spawn<T: EngineBound>(init: impl FnOnce() -> PyResult<T> + Send + 'static) -> PyResult<ThreadBound<T>> {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let join = thread::Builder::new()
+            .name("py_js_runtime-engine".to_string())
+            .spawn(move || {
+                while let Ok(job) = receiver.recv() {
+                    // 任务中的 panic 只结束该任务，等待方会收到错误
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                }
+            })
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to start engine thread: {}", e)))?;
+        let engine = Arc::new(Self { jobs, thread: join.thread().id() });
+        let current = Arc::downgrade(&engine);
+        let value = engine.run(move || {
+            CURRENT.with(|slot| *slot.borrow_mut() = current);
+            // SAFETY: 值移到调用方线程后直接存入绑定引擎线程的 ThreadBound，不会在调用方线程上被访问
+            init().map(|value| unsafe { ToOwner::new(value) })
+        })??.into_inner();
+        Ok(ThreadBound {
+            value: ManuallyDrop::new(value),
+            thread: engine.thread,
+            engine: Some(engine),
+        })
+    }
+
+    // 在引擎线程上执行 f 并等待结果，等待期间释放 GIL；已在引擎线程上时直接执行（重入）
+    fn run<R: Send>(&self, f: impl FnOnce() -> R + Send) -> PyResult<R> {
+        if thread::current().id() == self.thread {
+            return Ok(f());
+        }
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let job = ScopedJob { f: Some(f), result: sender };
+        let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || job.run());
+        // SAFETY: 通道的唯一发送端在任务内，且在闭包析构之后才释放（见 ScopedJob）。
+        // 下面的 recv 只会在收到结果或发送端释放后返回，此时任务借用的数据已不再被使用
+        let job: Job = unsafe { std::mem::transmute::<Box<dyn FnOnce() + Send + '_>, Job>(job) };
+        self.jobs.send(job)
+            .map_err(|_| PyRuntimeError::new_err("Engine thread has stopped"))?;
+        without_gil(|| receiver.recv())
+            .map_err(|_| PyRuntimeError::new_err("Engine thread stopped or panicked while handling the call"))
+    }
+}
+
+// 绑定到创建线程的值（isolate 及其句柄只能在所属线程上访问）。
+// 在引擎线程上创建时，其他线程的访问经 EngineThread 转交执行，释放也转交回引擎线程
+pub struct ThreadBound<T: EngineBound> {
+    value: ManuallyDrop<T>,
+    thread: ThreadId,
+    engine: Option<Arc<EngineThread>>,
+}
+
+// SAFETY: 值只在所属线程上被访问：get 检查当前线程，run 在所属线程上执行闭包，
+// Drop 在所属线程上析构或交给引擎线程析构，无法转交时泄漏；其他线程只持有与传递该结构
+unsafe impl<T: EngineBound> Send for ThreadBound<T> {}
+unsafe impl<T: EngineBound> Sync for ThreadBound<T> {}
+
+impl<T: EngineBound> ThreadBound<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+            thread: thread::current().id(),
+            engine: CURRENT.with(|slot| slot.borrow().upgrade()),
+        }
+    }
+
+    // 仅在所属线程上可以直接访问
+    pub fn get(&self) -> Option<&T> {
+        (thread::current().id() == self.thread).then_some(&*self.value)
+    }
+
+    // 在所属线程上对值执行 f
+    pub fn run<R: Send>(&self, f: impl FnOnce(&T) -> R + Send) -> PyResult<R> {
+        if let Some(value) = self.get() {
+            return Ok(f(value));
+        }
+        let engine = self.engine.as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Object can only be used on the thread that created it"))?;
+        // engine 存在时所属线程就是引擎线程，闭包在该线程上解引用值
+        engine.run(move || f(&*self.value))
+    }
+}
+
+impl<T: EngineBound> Drop for ThreadBound<T> {
+    fn drop(&mut self) {
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+        if thread::current().id() == self.thread {
+            return drop(value);
+        }
+        // SAFETY: 值只会在引擎线程（所属线程）上析构，或在此泄漏
+        let value = unsafe { ToOwner::new(value) };
+        match &self.engine {
+            Some(engine) => {
+                // 引擎线程已退出时 isolate 也已销毁，只能泄漏
+                if let Err(job) = engine.jobs.send(Box::new(move || drop(value.into_inner()))) {
+                    std::mem::forget(job);
+                }
+            }
+            None => std::mem::forget(value),
+        }
+    }
+}
@@ -30,7 +30,7 @@ function require(path) {
     }
     
     // 通知 Rust 加载模块
-    let moduleCode = _loadModule(resolvedPath);
+    let moduleCode = Deno.core.ops.op_load_module(resolvedPath);
     if (!moduleCode) {
         throw new Error(`Module not found: ${resolvedPath}`);
     }
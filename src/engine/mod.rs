@@ -1,4 +1,13 @@
 pub mod v8engine;
+pub mod virtual_loader;
+pub mod py_ops;
+pub mod py_modules;
+pub mod py_async;
+pub mod runtime_cell;
+pub mod engine_thread;
+pub mod process;
+pub mod transpile;
+pub mod source_map;
 // pub mod npm_loader;
 // pub mod asyncengine;
 // pub mod pool;
\ No newline at end of file
@@ -0,0 +1,508 @@
+use parking_lot::Mutex;
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyException, PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::types::{PyDict, PyTuple, PyType};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::engine::v8engine::{without_gil, JsEngine, PyContext};
+use crate::types::error::{JsException, JsWorkerError};
+use crate::types::wire::{read_frame, write_frame, Value};
+
+// 子进程的启动脚本：协议输出改用复制出的 stdout，原 stdout 指向 stderr，
+// 避免 console.log 等输出混入协议数据
+const BOOTSTRAP: &str = "
+import os, sys
+sys.path[:0] = {paths}
+out = os.dup(1)
+os.dup2(2, 1)
+if os.name == 'nt':
+    import msvcrt
+    out = msvcrt.get_osfhandle(out)
+import py_js_runtime
+py_js_runtime._serve_worker(out)
+";
+
+// 请求为 [操作名, 参数...]；响应为 [true, 结果] 或 [false, 异常]，异常的编码见 encode_error
+fn request(op: &str, args: impl IntoIterator<Item = Value>) -> Value {
+    Value::List(std::iter::once(Value::str(op)).chain(args).collect())
+}
+
+fn opt_str(value: Option<String>) -> Value {
+    value.map(Value::Str).unwrap_or(Value::None)
+}
+
+enum Failure {
+    // 子进程退出或协议损坏
+    Crashed(String),
+    // 子进程中抛出的异常，由 encode_error 编码
+    Remote(Value),
+}
+
+impl From<Failure> for PyErr {
+    fn from(failure: Failure) -> PyErr {
+        match failure {
+            Failure::Crashed(message) => JsWorkerError::new_err(message),
+            Failure::Remote(error) => Python::with_gil(|py| match decode_error(py, error) {
+                Ok(error) => PyErr::from_value(error),
+                Err(e) => e,
+            }),
+        }
+    }
+}
+
+// 异常编码为 [类型名, 消息, args, 属性, 异常类型的属性]：
+// 属性取自实例的 __dict__（如 JsException 的 name、stack 与 JS 错误的其余属性），无法传递的值被跳过；
+// 值为异常的属性（如 cause）单独递归编码
+fn encode_error(error: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let args = Value::from_py(&error.getattr("args")?).unwrap_or(Value::None);
+    let mut attrs = Vec::new();
+    let mut errors = Vec::new();
+    if let Ok(dict) = error.getattr("__dict__")?.downcast_into::<PyDict>() {
+        for (key, value) in dict.iter() {
+            let key = Value::from_py(&key)?;
+            if value.is_instance_of::<pyo3::exceptions::PyBaseException>() {
+                errors.push((key, encode_error(&value)?));
+            } else if let Ok(value) = Value::from_py(&value) {
+                attrs.push((key, value));
+            }
+        }
+    }
+    Ok(Value::List(vec![
+        Value::str(error.get_type().name()?.to_string()),
+        Value::str(error.str()?.to_string()),
+        args,
+        Value::Dict(attrs),
+        Value::Dict(errors),
+    ]))
+}
+
+// 还原为同类型的异常：JsException、JsWorkerError 与内置异常按类型名查找，其余类型转为 RuntimeError
+fn decode_error(py: Python<'_>, error: Value) -> PyResult<Bound<'_, PyAny>> {
+    let invalid = || JsWorkerError::new_err("Invalid error from JS worker process");
+    let Value::List(items) = error else {
+        return Err(invalid());
+    };
+    let [kind, message, args, attrs, errors] = <[Value; 5]>::try_from(items).map_err(|_| invalid())?;
+    let (kind, message) = (kind.as_str()?, message.as_str()?);
+    let args = match args.into_py(py)?.into_bound(py).downcast_into::<PyTuple>() {
+        Ok(args) => args,
+        Err(_) => PyTuple::new(py, [message])?,
+    };
+    let error = match error_type(py, kind).and_then(|ty| ty.call1(args).ok()) {
+        Some(error) => error,
+        None => PyRuntimeError::new_err(format!("{}: {}", kind, message)).into_value(py).into_bound(py).into_any(),
+    };
+    if let (Value::Dict(attrs), Value::Dict(errors)) = (attrs, errors) {
+        for (key, value) in attrs {
+            error.setattr(key.as_str()?, value.into_py(py)?)?;
+        }
+        for (key, value) in errors {
+            error.setattr(key.as_str()?, decode_error(py, value)?)?;
+        }
+    }
+    Ok(error)
+}
+
+fn error_type<'py>(py: Python<'py>, kind: &str) -> Option<Bound<'py, PyType>> {
+    match kind {
+        "JsException" => Some(py.get_type::<JsException>()),
+        "JsWorkerError" => Some(py.get_type::<JsWorkerError>()),
+        _ => py.import("builtins").ok()?
+            .getattr(kind).ok()?
+            .downcast_into::<PyType>().ok()
+            .filter(|ty| ty.is_subclass_of::<PyException>().unwrap_or(false)),
+    }
+}
+
+struct WorkerProcess {
+    child: Child,
+    input: BufWriter<ChildStdin>,
+    // 后台线程逐帧读取子进程的输出，exchange 可以限时等待响应
+    output: Receiver<io::Result<Value>>,
+}
+
+impl WorkerProcess {
+    fn exchange(&mut self, request: &Value, timeout: Option<Duration>) -> Result<Value, Failure> {
+        write_frame(&mut self.input, request).map_err(|e| self.crashed(e))?;
+        let response = match timeout {
+            Some(timeout) => self.output.recv_timeout(timeout),
+            None => self.output.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match response {
+            Ok(response) => response.map_err(|e| self.crashed(e)).and_then(Self::response),
+            Err(RecvTimeoutError::Timeout) => Err(self.timed_out(timeout.unwrap_or_default())),
+            Err(RecvTimeoutError::Disconnected) => Err(self.crashed(io::ErrorKind::UnexpectedEof.into())),
+        }
+    }
+
+    fn response(response: Value) -> Result<Value, Failure> {
+        let Value::List(mut items) = response else {
+            return Err(Failure::Crashed("Invalid response from JS worker process".to_string()));
+        };
+        match (items.first(), items.len()) {
+            (Some(Value::Bool(true)), 2) => Ok(items.pop().unwrap()),
+            (Some(Value::Bool(false)), 2) => Err(Failure::Remote(items.pop().unwrap())),
+            _ => Err(Failure::Crashed("Invalid response from JS worker process".to_string())),
+        }
+    }
+
+    fn crashed(&mut self, error: io::Error) -> Failure {
+        let _ = self.child.kill();
+        let status = match self.child.wait() {
+            Ok(status) => status.to_string(),
+            Err(_) => error.to_string(),
+        };
+        Failure::Crashed(format!(
+            "JS worker process exited unexpectedly ({}); it will be restarted on the next call",
+            status
+        ))
+    }
+
+    // 子进程未在期限内响应（死循环或卡住）时结束它，按崩溃处理
+    fn timed_out(&mut self, timeout: Duration) -> Failure {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        Failure::Crashed(format!(
+            "JS worker process did not respond within {:?} and was killed; it will be restarted on the next call",
+            timeout
+        ))
+    }
+}
+
+impl Drop for WorkerProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+struct WorkerState {
+    process: Option<WorkerProcess>,
+    // 每次崩溃后加一，旧进程中创建的上下文随之失效
+    generation: u64,
+}
+
+// 在子进程中运行的 JsRuntime：V8 崩溃、OOM 只会结束子进程，下次调用时自动重启
+pub struct ProcessRuntime {
+    executable: String,
+    bootstrap: String,
+    // 重启后按顺序重放的请求：init 与注册的 JS 转换器
+    setup: Mutex<Vec<Value>>,
+    // 每个请求的响应期限，None 表示不限时
+    timeout: Option<Duration>,
+    state: Mutex<WorkerState>,
+    // 已释放、待通知子进程的上下文，(generation, id)
+    released: Mutex<Vec<(u64, i64)>>,
+}
+
+impl ProcessRuntime {
+    pub fn new(py: Python<'_>, init: Value, timeout: Option<Duration>) -> PyResult<Arc<Self>> {
+        let sys = py.import("sys")?;
+        let paths = sys.getattr("path")?.repr()?.to_string();
+        let runtime = Arc::new(Self {
+            executable: sys.getattr("executable")?.extract()?,
+            bootstrap: BOOTSTRAP.replace("{paths}", &paths),
+            setup: Mutex::new(vec![request("init", [init])]),
+            timeout,
+            state: Mutex::new(WorkerState { process: None, generation: 0 }),
+            released: Mutex::new(Vec::new()),
+        });
+        // 立即启动，选项错误在构造时抛出
+        without_gil(|| runtime.exchange(None, None))?;
+        Ok(runtime)
+    }
+
+    fn spawn(&self) -> Result<WorkerProcess, Failure> {
+        let mut child = Command::new(&self.executable)
+            .arg("-c")
+            .arg(&self.bootstrap)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| Failure::Crashed(format!("Failed to start JS worker process: {}", e)))?;
+        let input = BufWriter::new(child.stdin.take().unwrap());
+        let mut reader = BufReader::new(child.stdout.take().unwrap());
+        let (sender, output) = mpsc::channel();
+        // 子进程退出后读到 EOF，线程随之结束
+        std::thread::spawn(move || loop {
+            let frame = read_frame(&mut reader);
+            let failed = frame.is_err();
+            if sender.send(frame).is_err() || failed {
+                break;
+            }
+        });
+        let mut process = WorkerProcess { child, input, output };
+        for request in self.setup.lock().iter() {
+            process.exchange(request, self.timeout)?;
+        }
+        Ok(process)
+    }
+
+    // 发送请求并等待响应（调用方已释放 GIL）；generation 为 Some 时要求子进程未重启过。
+    // request 为 None 时只确保子进程已启动
+    fn exchange(&self, generation: Option<u64>, request: Option<&Value>) -> Result<(Value, u64), Failure> {
+        let mut state = self.state.lock();
+        if generation.is_some_and(|generation| generation != state.generation) {
+            return Err(Failure::Crashed("Context belongs to a JS worker process that has been restarted".to_string()));
+        }
+        if state.process.is_none() {
+            state.process = Some(self.spawn()?);
+        }
+        let current = state.generation;
+        let released: Vec<Value> = std::mem::take(&mut *self.released.lock())
+            .into_iter()
+            .filter(|(generation, _)| *generation == current)
+            .map(|(_, id)| Value::Int(id))
+            .collect();
+        let process = state.process.as_mut().unwrap();
+        let mut result = Ok(Value::None);
+        if !released.is_empty() {
+            result = process.exchange(&request_release(released), self.timeout);
+        }
+        if let (Ok(_), Some(request)) = (&result, request) {
+            result = process.exchange(request, self.timeout);
+        }
+        if let Err(Failure::Crashed(_)) = &result {
+            state.process = None;
+            state.generation += 1;
+        }
+        result.map(|value| (value, current))
+    }
+
+    pub fn call(&self, request: &Value) -> PyResult<Value> {
+        Ok(without_gil(|| self.exchange(None, Some(request)))?.0)
+    }
+
+    pub fn eval(&self, py: Python<'_>, code: String, filename: Option<String>) -> PyResult<PyObject> {
+        self.call(&request("eval", [Value::Str(code), opt_str(filename)]))?.into_py(py)
+    }
+
+    pub fn compile_code(self: &Arc<Self>, code: String, filename: Option<String>) -> PyResult<PyContext> {
+        self.compile(request("compile_code", [Value::Str(code), opt_str(filename)]))
+    }
+
+    pub fn compile_module(self: &Arc<Self>, code: String, specifier: Option<String>, base_dir: Option<String>) -> PyResult<PyContext> {
+        self.compile(request("compile_module", [Value::Str(code), opt_str(specifier), opt_str(base_dir)]))
+    }
+
+    pub fn compile_file(self: &Arc<Self>, file_path: String) -> PyResult<PyContext> {
+        self.compile(request("compile_file", [Value::Str(file_path)]))
+    }
+
+    fn compile(self: &Arc<Self>, request: Value) -> PyResult<PyContext> {
+        match without_gil(|| self.exchange(None, Some(&request)))? {
+            (Value::Int(id), generation) => Ok(PyContext::remote(RemoteContext { runtime: self.clone(), id, generation })),
+            _ => Err(Failure::Crashed("Invalid response from JS worker process".to_string()).into()),
+        }
+    }
+
+    // 只支持 JS 实现的转换器，子进程重启后重新注册
+    pub fn register_js_converter(&self, constructor_name: String, js: String) -> PyResult<()> {
+        let request = request("register_js_converter", [Value::Str(constructor_name), Value::Str(js)]);
+        self.call(&request)?;
+        self.setup.lock().push(request);
+        Ok(())
+    }
+}
+
+fn request_release(ids: Vec<Value>) -> Value {
+    request("release", [Value::List(ids)])
+}
+
+// 子进程中编译得到的上下文
+pub struct RemoteContext {
+    runtime: Arc<ProcessRuntime>,
+    id: i64,
+    generation: u64,
+}
+
+impl RemoteContext {
+    fn call(&self, request: &Value) -> PyResult<Value> {
+        Ok(without_gil(|| self.runtime.exchange(Some(self.generation), Some(request)))?.0)
+    }
+
+    fn args(args: &Bound<'_, PyTuple>) -> PyResult<Value> {
+        args.iter().map(|arg| Value::from_py(&arg)).collect::<PyResult<_>>().map(Value::List)
+    }
+
+    pub fn call_function(&self, py: Python<'_>, name: String, args: &Bound<'_, PyTuple>, transfer: &str, await_promise: bool) -> PyResult<PyObject> {
+        let request = request("call_function", [Value::Int(self.id), Value::Str(name), Self::args(args)?, Value::str(transfer), Value::Bool(await_promise)]);
+        self.call(&request)?.into_py(py)
+    }
+
+    pub fn call_function_json(&self, name: String, args: &Bound<'_, PyTuple>, await_promise: bool) -> PyResult<String> {
+        let request = request("call_function_json", [Value::Int(self.id), Value::Str(name), Self::args(args)?, Value::Bool(await_promise)]);
+        match self.call(&request)? {
+            Value::Str(json) => Ok(json),
+            _ => Err(Failure::Crashed("Invalid response from JS worker process".to_string()).into()),
+        }
+    }
+
+    pub fn get_property(&self, py: Python<'_>, expr: String) -> PyResult<PyObject> {
+        self.call(&request("get_property", [Value::Int(self.id), Value::Str(expr)]))?.into_py(py)
+    }
+}
+
+impl Drop for RemoteContext {
+    fn drop(&mut self) {
+        // 不在析构中等待子进程，随下一个请求一起通知
+        self.runtime.released.lock().push((self.generation, self.id));
+    }
+}
+
+// 子进程一侧：在当前线程创建 JsEngine，逐个处理来自 stdin 的请求
+#[derive(Default)]
+struct Worker {
+    engine: Option<JsEngine>,
+    contexts: HashMap<i64, PyContext>,
+    next_id: i64,
+}
+
+fn arg(args: &[Value], index: usize) -> &Value {
+    args.get(index).unwrap_or(&Value::None)
+}
+
+impl Worker {
+    fn engine(&self) -> PyResult<&JsEngine> {
+        self.engine.as_ref().ok_or_else(|| PyRuntimeError::new_err("JS worker is not initialized"))
+    }
+
+    fn context(&self, id: &Value) -> PyResult<&PyContext> {
+        match id {
+            Value::Int(id) => self.contexts.get(id),
+            _ => None,
+        }
+        .ok_or_else(|| PyKeyError::new_err("Unknown context"))
+    }
+
+    fn insert(&mut self, context: PyContext) -> Value {
+        self.next_id += 1;
+        self.contexts.insert(self.next_id, context);
+        Value::Int(self.next_id)
+    }
+
+    fn tuple<'py>(py: Python<'py>, args: &Value) -> PyResult<Bound<'py, PyTuple>> {
+        match args.clone().into_py(py)?.into_bound(py).downcast_into::<pyo3::types::PyList>() {
+            Ok(list) => Ok(list.to_tuple()),
+            Err(_) => Err(PyValueError::new_err("Expected an argument list")),
+        }
+    }
+
+    fn handle(&mut self, py: Python<'_>, request: Value) -> PyResult<Value> {
+        let Value::List(items) = request else {
+            return Err(PyValueError::new_err("Invalid request"));
+        };
+        let (op, args) = items.split_first().ok_or_else(|| PyValueError::new_err("Invalid request"))?;
+        let result = match op.as_str()? {
+            "init" => {
+                let Value::Dict(options) = arg(args, 0) else {
+                    return Err(PyValueError::new_err("Invalid init options"));
+                };
+                let options: HashMap<String, Value> = options.iter()
+                    .map(|(key, value)| Ok((key.as_str()?.to_string(), value.clone())))
+                    .collect::<PyResult<_>>()?;
+                let option = |name: &str| options.get(name).cloned().unwrap_or(Value::None);
+                let modules = match option("modules") {
+                    Value::Dict(modules) => Some(modules.iter()
+                        .map(|(name, source)| Ok((name.as_str()?.to_string(), source.as_str()?.to_string())))
+                        .collect::<PyResult<HashMap<_, _>>>()?),
+                    _ => None,
+                };
+                let flag = |name: &str| matches!(option(name), Value::Bool(true));
+                let text = |name: &str| option(name).as_str().map(str::to_string);
+                let max_nesting_depth = match option("max_nesting_depth") {
+                    Value::Int(depth) => usize::try_from(depth).map_err(|_| PyValueError::new_err("Invalid max_nesting_depth"))?,
+                    _ => return Err(PyValueError::new_err("Invalid max_nesting_depth")),
+                };
+                self.engine = Some(JsEngine::new(
+                    py, modules, None, &text("int_overflow")?, &text("number_mode")?, &text("typed_array")?,
+                    flag("zero_copy"), &text("map_mode")?, flag("prototype_getters"), None, "host", max_nesting_depth,
+                )?);
+                py.None()
+            }
+            "eval" => self.engine()?.eval(py, arg(args, 0).as_str()?.to_string(), arg(args, 1).as_opt_str()?.map(str::to_string))?,
+            "compile_code" => {
+                let context = self.engine()?.compile_code(py, arg(args, 0).as_str()?.to_string(), arg(args, 1).as_opt_str()?.map(str::to_string))?;
+                return Ok(self.insert(context));
+            }
+            "compile_module" => {
+                let context = self.engine()?.compile_module(
+                    py,
+                    arg(args, 0).as_str()?.to_string(),
+                    arg(args, 1).as_opt_str()?.map(str::to_string),
+                    arg(args, 2).as_opt_str()?.map(str::to_string),
+                )?;
+                return Ok(self.insert(context));
+            }
+            "compile_file" => {
+                let context = self.engine()?.compile_file(py, arg(args, 0).as_str()?.to_string())?;
+                return Ok(self.insert(context));
+            }
+            "register_js_converter" => {
+                self.engine()?.register_js_converter(arg(args, 0).as_str()?.to_string(), None, Some(arg(args, 1).as_str()?.to_string()))?;
+                py.None()
+            }
+            "call_function" => {
+                let call_args = Self::tuple(py, arg(args, 2))?.unbind();
+                self.context(arg(args, 0))?.call_function(py, arg(args, 1).as_str()?.to_string(), call_args, arg(args, 3).as_str()?, matches!(arg(args, 4), Value::Bool(true)))?
+            }
+            "call_function_json" => {
+                let call_args = Self::tuple(py, arg(args, 2))?.unbind();
+                let json = self.context(arg(args, 0))?.call_function_json(py, arg(args, 1).as_str()?.to_string(), call_args, matches!(arg(args, 3), Value::Bool(true)))?;
+                return Ok(Value::Str(json));
+            }
+            "get_property" => self.context(arg(args, 0))?.get_property(py, arg(args, 1).as_str()?.to_string())?,
+            "release" => {
+                if let Value::List(ids) = arg(args, 0) {
+                    for id in ids {
+                        if let Value::Int(id) = id {
+                            self.contexts.remove(id);
+                        }
+                    }
+                }
+                py.None()
+            }
+            op => return Err(PyValueError::new_err(format!("Unknown request: {}", op))),
+        };
+        Value::from_py(result.bind(py))
+    }
+}
+
+// output 为协议输出的文件描述符（Windows 上为句柄），由启动脚本传入
+#[pyfunction]
+#[pyo3(name = "_serve_worker")]
+pub fn serve_worker(py: Python<'_>, output: i64) -> PyResult<()> {
+    let mut output = BufWriter::new(unsafe { output_file(output) });
+    let mut worker = Worker::default();
+    loop {
+        let request = match py.allow_threads(|| read_frame(&mut io::stdin().lock())) {
+            Ok(request) => request,
+            // 父进程关闭管道
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(PyRuntimeError::new_err(e.to_string())),
+        };
+        let response = match worker.handle(py, request) {
+            Ok(value) => Value::List(vec![Value::Bool(true), value]),
+            Err(err) => Value::List(vec![Value::Bool(false), encode_error(err.value(py).as_any())?]),
+        };
+        write_frame(&mut output, &response).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    }
+}
+
+#[cfg(unix)]
+unsafe fn output_file(output: i64) -> File {
+    use std::os::fd::FromRawFd;
+    File::from_raw_fd(output as i32)
+}
+
+#[cfg(windows)]
+unsafe fn output_file(output: i64) -> File {
+    use std::os::windows::io::{FromRawHandle, RawHandle};
+    File::from_raw_handle(output as usize as RawHandle)
+}
@@ -0,0 +1,151 @@
+use deno_core::{op2, v8, OpState};
+use deno_error::JsErrorBox;
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyType};
+use pyo3_async_runtimes::TaskLocals;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use crate::types::error::JsError;
+use crate::types::proxy;
+
+// JS 侧等待 Python awaitable 的包装函数：异步 op 等待完成，再由同步 op 取回结果并转换
+pub const AWAIT_JS: &str = "(id) => Deno.core.ops.op_py_await(id).then(() => Deno.core.ops.op_py_settled(id))";
+
+// 运行 Python 协程的 asyncio 事件循环，在后台守护线程中 run_forever，进程内共享
+static EVENT_LOOP: GILOnceCell<PyObject> = GILOnceCell::new();
+
+// is_awaitable 对每个无法直接转换的对象都会调用，缓存所需的函数与类型
+static IS_AWAITABLE: GILOnceCell<PyObject> = GILOnceCell::new();
+static CONCURRENT_FUTURE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+fn concurrent_future(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    CONCURRENT_FUTURE.import(py, "concurrent.futures", "Future")
+}
+
+type PyFuture = Pin<Box<dyn Future<Output = PyResult<PyObject>>>>;
+
+// 已交给 JS 的 Python awaitable，按 id 索引；同时保存在 OpState 与 isolate slot 中
+#[derive(Default)]
+pub struct PyAwaitables {
+    next_id: Cell<u32>,
+    pending: RefCell<HashMap<u32, PyFuture>>,
+    settled: RefCell<HashMap<u32, PyObject>>,
+    await_fn: RefCell<Option<v8::Global<v8::Function>>>,
+}
+
+impl PyAwaitables {
+    pub fn get(isolate: &v8::Isolate) -> Option<Rc<Self>> {
+        isolate.get_slot::<Rc<Self>>().cloned()
+    }
+
+    // await_fn 为执行 AWAIT_JS 得到的函数
+    pub fn install(self: Rc<Self>, scope: &mut v8::HandleScope, await_fn: v8::Local<v8::Value>) -> Result<(), JsError> {
+        let await_fn = v8::Local::<v8::Function>::try_from(await_fn)
+            .map_err(|_| JsError::RuntimeError("Invalid await function".to_string()))?;
+        *self.await_fn.borrow_mut() = Some(v8::Global::new(scope, await_fn));
+        scope.set_slot(self);
+        Ok(())
+    }
+
+    fn insert(&self, future: PyFuture) -> u32 {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        self.pending.borrow_mut().insert(id, future);
+        id
+    }
+}
+
+pub fn event_loop(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    EVENT_LOOP
+        .get_or_try_init(py, || {
+            let event_loop = py.import("asyncio")?.call_method0("new_event_loop")?;
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("target", event_loop.getattr("run_forever")?)?;
+            kwargs.set_item("name", "py_js_runtime-asyncio")?;
+            kwargs.set_item("daemon", true)?;
+            py.import("threading")?
+                .call_method("Thread", (), Some(&kwargs))?
+                .call_method0("start")?;
+            Ok(event_loop.unbind())
+        })
+        .map(|event_loop| event_loop.bind(py))
+}
+
+// concurrent.futures.Future 不是 awaitable，但同样可以转换
+pub fn is_awaitable(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let py = obj.py();
+    Ok(IS_AWAITABLE.import(py, "inspect", "isawaitable")?.call1((obj,))?.is_truthy()?
+        || obj.is_instance(concurrent_future(py)?)?)
+}
+
+// 调度 awaitable（沿用调用方的 contextvars），返回在其完成时就绪的 future
+// asyncio.Future 在其所属的事件循环中等待，其余 awaitable 在后台事件循环中运行
+pub fn into_future(awaitable: &Bound<'_, PyAny>) -> PyResult<impl Future<Output = PyResult<PyObject>> + Send> {
+    let py = awaitable.py();
+    let asyncio = py.import("asyncio")?;
+    let (event_loop, awaitable) = if asyncio.call_method1("isfuture", (awaitable,))?.is_truthy()? {
+        (awaitable.call_method0("get_loop")?, awaitable.clone())
+    } else if awaitable.is_instance(concurrent_future(py)?)? {
+        let event_loop = event_loop(py)?.clone();
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("loop", &event_loop)?;
+        let wrapped = asyncio.call_method("wrap_future", (awaitable,), Some(&kwargs))?;
+        (event_loop, wrapped)
+    } else {
+        (event_loop(py)?.clone(), awaitable.clone())
+    };
+    let locals = TaskLocals::new(event_loop).copy_context(py)?;
+    pyo3_async_runtimes::into_future_with_locals(&locals, awaitable)
+}
+
+// Python awaitable 转为 JS Promise，awaitable 完成后 Promise 以其结果兑现
+pub fn awaitable_to_promise<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    awaitable: &Bound<'_, PyAny>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let awaitables = PyAwaitables::get(scope)
+        .ok_or_else(|| JsError::RuntimeError("Python awaitables are not supported here".to_string()))?;
+    let await_fn = awaitables.await_fn.borrow().clone()
+        .ok_or_else(|| JsError::RuntimeError("Python awaitables are not supported here".to_string()))?;
+    let id = awaitables.insert(Box::pin(into_future(awaitable)?));
+    let await_fn = v8::Local::new(scope, await_fn);
+    let recv = v8::undefined(scope).into();
+    let id = v8::Integer::new_from_unsigned(scope, id).into();
+    await_fn.call(scope, recv, &[id])
+        .ok_or_else(|| JsError::ExecutionError("Failed to create promise".to_string()).into())
+}
+
+#[op2(async)]
+async fn op_py_await(state: Rc<RefCell<OpState>>, #[smi] id: u32) -> Result<(), JsErrorBox> {
+    let awaitables = state.borrow().borrow::<Rc<PyAwaitables>>().clone();
+    let future = awaitables.pending.borrow_mut().remove(&id)
+        .ok_or_else(|| JsErrorBox::type_error(format!("Unknown awaitable: {}", id)))?;
+    let result = future.await.map_err(|e| JsErrorBox::generic(e.to_string()))?;
+    awaitables.settled.borrow_mut().insert(id, result);
+    Ok(())
+}
+
+// 取回已完成的结果，按 proxy::to_js 规则转换
+#[op2]
+fn op_py_settled<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    #[state] awaitables: &Rc<PyAwaitables>,
+    #[smi] id: u32,
+) -> Result<v8::Local<'a, v8::Value>, JsErrorBox> {
+    let result = awaitables.settled.borrow_mut().remove(&id)
+        .ok_or_else(|| JsErrorBox::type_error(format!("Unknown awaitable: {}", id)))?;
+    Python::with_gil(|py| proxy::to_js(scope, result.bind(py)).map_err(|e| JsErrorBox::generic(e.to_string())))
+}
+
+deno_core::extension!(
+    py_async,
+    ops = [op_py_await, op_py_settled],
+    options = { awaitables: Rc<PyAwaitables> },
+    state = |state, options| {
+        state.put(options.awaitables);
+    },
+);
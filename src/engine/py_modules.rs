@@ -0,0 +1,137 @@
+use deno_core::{op2, v8, ModuleSpecifier};
+use deno_error::JsErrorBox;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::types::convert::{js_to_py, py_to_js};
+
+// Python 模块使用的 URL scheme，例如 import { sha256 } from "python:hashlib_helpers"
+pub const PYTHON_SCHEME: &str = "python";
+
+// 注册到 JS 的 Python 模块或 dict，按模块名索引
+#[derive(Default)]
+pub struct PythonModules {
+    modules: RefCell<HashMap<String, PyObject>>,
+}
+
+impl PythonModules {
+    pub fn register(&self, name: String, module: PyObject) {
+        self.modules.borrow_mut().insert(name, module);
+    }
+
+    fn get(&self, py: Python<'_>, name: &str) -> Option<PyObject> {
+        self.modules.borrow().get(name).map(|module| module.clone_ref(py))
+    }
+
+    // 导出名：dict 取全部字符串键；模块优先取 __all__，否则取不以下划线开头的属性
+    fn export_names(module: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        if let Ok(dict) = module.downcast::<PyDict>() {
+            return Ok(dict.keys().iter().filter_map(|key| key.extract::<String>().ok()).collect());
+        }
+        if let Ok(all) = module.getattr("__all__") {
+            return all.extract();
+        }
+        let names: Vec<String> = module.dir()?.extract()?;
+        Ok(names.into_iter().filter(|name| !name.starts_with('_')).collect())
+    }
+
+    fn export<'py>(module: &Bound<'py, PyAny>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+        match module.downcast::<PyDict>() {
+            Ok(dict) => dict.as_any().get_item(name),
+            Err(_) => module.getattr(name),
+        }
+    }
+
+    // 生成 python: 模块的 JS 源码：函数导出经 op 调用 Python，其余导出在加载时转换为 JS 值
+    pub fn module_source(&self, specifier: &ModuleSpecifier) -> Result<Option<String>, String> {
+        let name = specifier.path();
+        Python::with_gil(|py| {
+            let Some(module) = self.get(py, name) else {
+                return Ok(None);
+            };
+            let module = module.bind(py);
+            let quoted_module = serde_json::Value::from(name).to_string();
+            let mut source = String::from("const { op_py_module_call, op_py_module_export } = Deno.core.ops;\n");
+            let mut exports = Vec::new();
+            for (i, export) in Self::export_names(module).map_err(|e| e.to_string())?.iter().enumerate() {
+                let value = Self::export(module, export).map_err(|e| e.to_string())?;
+                let quoted = serde_json::Value::from(export.as_str()).to_string();
+                if value.is_callable() {
+                    source.push_str(&format!(
+                        "const __{} = (...args) => op_py_module_call({}, {}, args);\n",
+                        i, quoted_module, quoted
+                    ));
+                } else {
+                    source.push_str(&format!(
+                        "const __{} = op_py_module_export({}, {});\n",
+                        i, quoted_module, quoted
+                    ));
+                }
+                exports.push((i, quoted));
+            }
+            // 任意字符串都可以作为导出名；default 导出为包含全部成员的对象（除非模块自身导出了 default）
+            let named = exports.iter().map(|(i, quoted)| format!("__{} as {}", i, quoted)).collect::<Vec<_>>();
+            source.push_str(&format!("export {{ {} }};\n", named.join(", ")));
+            if !exports.iter().any(|(_, quoted)| quoted == "\"default\"") {
+                let members = exports.iter().map(|(i, quoted)| format!("{}: __{}", quoted, i)).collect::<Vec<_>>();
+                source.push_str(&format!("export default {{ {} }};\n", members.join(", ")));
+            }
+            Ok(Some(source))
+        })
+    }
+}
+
+fn lookup<'py>(py: Python<'py>, modules: &PythonModules, module: &str, name: &str) -> Result<Bound<'py, PyAny>, JsErrorBox> {
+    let module = modules.get(py, module)
+        .ok_or_else(|| JsErrorBox::type_error(format!("Python module not found: {}", module)))?;
+    PythonModules::export(module.bind(py), name).map_err(|e| JsErrorBox::generic(e.to_string()))
+}
+
+// 读取常量导出，按 py_to_js 规则转换
+#[op2]
+fn op_py_module_export<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    #[state] modules: &Rc<PythonModules>,
+    #[string] module: String,
+    #[string] name: String,
+) -> Result<v8::Local<'a, v8::Value>, JsErrorBox> {
+    Python::with_gil(|py| {
+        let value = lookup(py, modules, &module, &name)?;
+        py_to_js(scope, &value).map_err(|e| JsErrorBox::generic(e.to_string()))
+    })
+}
+
+// 调用函数导出，参数经 js_to_py 转换，返回值经 py_to_js 转换（awaitable 转为 Promise）
+#[op2]
+fn op_py_module_call<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    #[state] modules: &Rc<PythonModules>,
+    #[string] module: String,
+    #[string] name: String,
+    args: v8::Local<'a, v8::Array>,
+) -> Result<v8::Local<'a, v8::Value>, JsErrorBox> {
+    Python::with_gil(|py| {
+        let func = lookup(py, modules, &module, &name)?;
+        let mut py_args = Vec::with_capacity(args.length() as usize);
+        for i in 0..args.length() {
+            let arg = args.get_index(scope, i).unwrap_or_else(|| v8::undefined(scope).into());
+            py_args.push(js_to_py(py, scope, arg).map_err(|e| JsErrorBox::generic(e.to_string()))?);
+        }
+        let result = PyTuple::new(py, py_args)
+            .and_then(|args| func.call1(args))
+            .map_err(|e| JsErrorBox::generic(e.to_string()))?;
+        // 协程函数返回的协程经 py_to_js 转为 Promise
+        py_to_js(scope, &result).map_err(|e| JsErrorBox::generic(e.to_string()))
+    })
+}
+
+deno_core::extension!(
+    py_modules,
+    ops = [op_py_module_call, op_py_module_export],
+    options = { modules: Rc<PythonModules> },
+    state = |state, options| {
+        state.put(options.modules);
+    },
+);
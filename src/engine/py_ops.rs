@@ -0,0 +1,82 @@
+use deno_core::{op2, v8};
+use deno_error::JsErrorBox;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::types::convert::{js_to_py, py_to_js};
+
+// Python 实现的 op，参数与返回值按 js_to_py/py_to_js 转换
+// 返回的协程（协程函数或返回协程的普通函数）转为 Promise，在共享的 asyncio 事件循环中执行
+pub struct PyOps {
+    ops: HashMap<String, PyObject>,
+    // JS 侧挂载 op 的全局对象名
+    namespace: String,
+}
+
+impl PyOps {
+    pub fn new(ops: HashMap<String, PyObject>, namespace: String) -> Self {
+        Self { ops, namespace }
+    }
+
+    // 在 Deno.core.ops 与全局命名空间 globalThis[namespace] 上挂载各 op 的 JS 包装函数
+    pub fn bootstrap_script(&self) -> String {
+        let names: Vec<&str> = self.ops.keys().map(String::as_str).collect();
+        format!(
+            r#"(() => {{
+  const {{ op_py_call }} = Deno.core.ops;
+  const namespace = globalThis[{namespace}] ??= {{}};
+  for (const name of {names}) {{
+    const fn = (...args) => op_py_call(name, args);
+    Deno.core.ops[name] = fn;
+    namespace[name] = fn;
+  }}
+}})();"#,
+            namespace = serde_json::Value::from(self.namespace.as_str()),
+            names = serde_json::json!(names),
+        )
+    }
+
+    fn get(&self, py: Python<'_>, name: &str) -> Result<PyObject, JsErrorBox> {
+        self.ops
+            .get(name)
+            .map(|func| func.clone_ref(py))
+            .ok_or_else(|| JsErrorBox::type_error(format!("Unknown op: {}", name)))
+    }
+}
+
+fn to_js_error(err: PyErr) -> JsErrorBox {
+    JsErrorBox::generic(err.to_string())
+}
+
+// 参数经 js_to_py 转换，返回值经 py_to_js 转换，与其他调用路径使用相同的转换选项与转换器。
+// 返回的协程经 py_to_js 转为 Promise，不在 op 中阻塞等待：协程可能需要再次调用本 runtime
+#[op2]
+fn op_py_call<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    #[state] ops: &Rc<PyOps>,
+    #[string] name: String,
+    args: v8::Local<'a, v8::Array>,
+) -> Result<v8::Local<'a, v8::Value>, JsErrorBox> {
+    Python::with_gil(|py| {
+        let func = ops.get(py, &name)?;
+        let mut py_args = Vec::with_capacity(args.length() as usize);
+        for i in 0..args.length() {
+            let arg = args.get_index(scope, i).unwrap_or_else(|| v8::undefined(scope).into());
+            py_args.push(js_to_py(py, scope, arg).map_err(to_js_error)?);
+        }
+        let result = PyTuple::new(py, py_args)
+            .and_then(|args| func.call1(py, args))
+            .map_err(to_js_error)?;
+        py_to_js(scope, result.bind(py)).map_err(to_js_error)
+    })
+}
+
+deno_core::extension!(
+    py_ops,
+    ops = [op_py_call],
+    options = { ops: Rc<PyOps> },
+    state = |state, options| {
+        state.put(options.ops);
+    },
+);
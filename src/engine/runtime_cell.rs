@@ -0,0 +1,140 @@
+use deno_core::{v8, JsRuntime};
+use parking_lot::{Condvar, Mutex};
+use std::cell::UnsafeCell;
+use std::mem::ManuallyDrop;
+use std::thread::{self, ThreadId};
+use crate::engine::v8engine::without_gil;
+use crate::types::buffer::release_pending;
+use crate::types::error::JsError;
+
+// 默认的最大嵌套深度：Python 回调每次重新调用同一 runtime 计为一层
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 16;
+
+// runtime 的所有权：持有线程可在 JS 回调中重入（深度受限），其他线程按到达顺序排队等待
+pub struct RuntimeCell {
+    runtime: ManuallyDrop<UnsafeCell<JsRuntime>>,
+    // 嵌套调用经由这两项建立作用域：isolate 由 V8 分配，不在 JsRuntime 内部，
+    // 外层持有 &mut JsRuntime 时通过它访问 isolate 不会产生别名的 &mut JsRuntime
+    isolate: *mut v8::Isolate,
+    context: ManuallyDrop<v8::Global<v8::Context>>,
+    state: Mutex<LockState>,
+    released: Condvar,
+    max_depth: usize,
+}
+
+#[derive(Default)]
+struct LockState {
+    owner: Option<ThreadId>,
+    depth: usize,
+    // 排队号：next_ticket 分配给下一个到达的线程，serving 为下一个可获得 runtime 的线程
+    next_ticket: u64,
+    serving: u64,
+}
+
+impl RuntimeCell {
+    pub fn new(mut runtime: JsRuntime, max_depth: usize) -> Self {
+        let isolate = &mut **runtime.v8_isolate() as *mut v8::Isolate;
+        let context = ManuallyDrop::new(runtime.main_context());
+        Self {
+            runtime: ManuallyDrop::new(UnsafeCell::new(runtime)),
+            isolate,
+            context,
+            state: Mutex::new(LockState::default()),
+            released: Condvar::new(),
+            max_depth: max_depth.max(1),
+        }
+    }
+
+    pub fn enter(&self) -> Result<RuntimeGuard<'_>, JsError> {
+        let current = thread::current().id();
+        let mut state = self.state.lock();
+        if state.owner == Some(current) {
+            if state.depth >= self.max_depth {
+                return Err(JsError::RuntimeError(format!("Maximum nesting depth of {} exceeded", self.max_depth)));
+            }
+            state.depth += 1;
+            return Ok(RuntimeGuard { cell: self, depth: state.depth });
+        }
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        if state.owner.is_some() || state.serving != ticket {
+            drop(state);
+            // 持有线程可能需要 GIL 才能完成当前调用，排队期间释放 GIL
+            without_gil(|| {
+                let mut state = self.state.lock();
+                while state.owner.is_some() || state.serving != ticket {
+                    self.released.wait(&mut state);
+                }
+            });
+            state = self.state.lock();
+        }
+        state.owner = Some(current);
+        state.depth = 1;
+        state.serving += 1;
+        drop(state);
+        release_pending();
+        Ok(RuntimeGuard { cell: self, depth: 1 })
+    }
+}
+
+impl Drop for RuntimeCell {
+    fn drop(&mut self) {
+        // 销毁 isolate 时 V8 调用剩余 ArrayBuffer 的 deleter，随后释放对应的 Python 缓冲区
+        unsafe {
+            ManuallyDrop::drop(&mut self.context);
+            ManuallyDrop::drop(&mut self.runtime);
+        }
+        release_pending();
+    }
+}
+
+pub struct RuntimeGuard<'a> {
+    cell: &'a RuntimeCell,
+    depth: usize,
+}
+
+impl RuntimeGuard<'_> {
+    // 是否为 JS 回调中重入的调用
+    pub fn nested(&self) -> bool {
+        self.depth > 1
+    }
+
+    // 驱动事件循环、加载模块等操作只能在最外层进行：deno_core 的事件循环不可重入。
+    // 只有最外层 guard 会取得 &mut JsRuntime，嵌套的 guard 不会
+    pub fn runtime(&mut self) -> Result<&mut JsRuntime, JsError> {
+        if self.nested() {
+            return Err(JsError::RuntimeError("Cannot run the event loop inside a nested call".to_string()));
+        }
+        Ok(unsafe { &mut *self.cell.runtime.get() })
+    }
+
+    // 在 HandleScope 中执行 f。嵌套调用时外层回调的作用域仍然有效，
+    // 新作用域须建立在当前作用域之上，而不是从 isolate 根部创建
+    pub fn with_scope<R>(&mut self, f: impl FnOnce(&mut v8::HandleScope) -> R) -> R {
+        if !self.nested() {
+            let runtime = unsafe { &mut *self.cell.runtime.get() };
+            return f(&mut runtime.handle_scope());
+        }
+        // 外层停在 V8 回调中（可能仍持有 &mut JsRuntime），只会在内层结束后继续；
+        // 这里只经由裸指针访问 isolate，不触及 JsRuntime
+        let isolate = unsafe { &mut *self.cell.isolate };
+        let callback_scope = &mut unsafe { v8::CallbackScope::new(isolate) };
+        let handle_scope = &mut v8::HandleScope::new(callback_scope);
+        let context = v8::Local::new(handle_scope, &*self.cell.context);
+        let scope = &mut v8::ContextScope::new(handle_scope, context);
+        f(scope)
+    }
+}
+
+impl Drop for RuntimeGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.cell.state.lock();
+        state.depth -= 1;
+        if state.depth == 0 {
+            state.owner = None;
+            self.cell.released.notify_all();
+        }
+        drop(state);
+        release_pending();
+    }
+}
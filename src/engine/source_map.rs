@@ -0,0 +1,64 @@
+use base64::Engine;
+use deno_core::ModuleSpecifier;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// 按文件名保存 source map，错误堆栈据此映射回原始位置
+#[derive(Default)]
+pub struct SourceMapStore {
+    maps: RefCell<HashMap<String, Option<Vec<u8>>>>,
+}
+
+impl SourceMapStore {
+    pub fn insert(&self, file_name: &str, source_map: Vec<u8>) {
+        self.maps.borrow_mut().insert(file_name.to_string(), Some(source_map));
+    }
+
+    // 记录源码末尾 sourceMappingURL 指向的 source map（内联 data URL 或外部文件）
+    pub fn register_source(
+        &self,
+        specifier: &ModuleSpecifier,
+        code: &str,
+        read: impl Fn(&ModuleSpecifier) -> Option<String>,
+    ) {
+        let source_map = source_mapping_url(code).and_then(|url| load_source_map(specifier, url, read));
+        self.maps.borrow_mut().insert(specifier.to_string(), source_map);
+    }
+
+    pub fn contains(&self, file_name: &str) -> bool {
+        self.maps.borrow().contains_key(file_name)
+    }
+
+    pub fn get(&self, file_name: &str) -> Option<Vec<u8>> {
+        self.maps.borrow().get(file_name).cloned().flatten()
+    }
+}
+
+// 取最后一条 //# sourceMappingURL= 注释（兼容旧的 //@ 写法）
+fn source_mapping_url(code: &str) -> Option<&str> {
+    code.lines().rev().find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("//# sourceMappingURL=")
+            .or_else(|| line.strip_prefix("//@ sourceMappingURL="))
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+    })
+}
+
+fn load_source_map(
+    specifier: &ModuleSpecifier,
+    url: &str,
+    read: impl Fn(&ModuleSpecifier) -> Option<String>,
+) -> Option<Vec<u8>> {
+    if let Some(data) = url.strip_prefix("data:") {
+        let (meta, payload) = data.split_once(',')?;
+        return if meta.ends_with(";base64") {
+            base64::engine::general_purpose::STANDARD.decode(payload).ok()
+        } else {
+            Some(payload.as_bytes().to_vec())
+        };
+    }
+    // 外部 source map 相对于脚本自身解析
+    let map_specifier = specifier.join(url).ok()?;
+    read(&map_specifier).map(String::into_bytes)
+}
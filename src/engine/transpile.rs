@@ -0,0 +1,137 @@
+use deno_ast::{
+    EmitOptions, MediaType, ParseParams, SourceMapOption, TranspileModuleOptions, TranspileOptions,
+};
+use deno_core::ModuleSpecifier;
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+// 缓存的转译结果数量上限，超出后淘汰最早加入的条目
+const CACHE_CAPACITY: usize = 256;
+
+// 需要转译的源码类型：TypeScript 与 JSX
+pub fn transpile_media_type(specifier: &ModuleSpecifier) -> Option<MediaType> {
+    match MediaType::from_specifier(specifier) {
+        media_type @ (MediaType::TypeScript
+        | MediaType::Mts
+        | MediaType::Cts
+        | MediaType::Tsx
+        | MediaType::Jsx) => Some(media_type),
+        _ => None,
+    }
+}
+
+struct Transpiled {
+    code: String,
+    source_map: Option<Vec<u8>>,
+    // 生成 source map 时使用的模块标识
+    specifier: String,
+}
+
+// 缓存键为 (源码, 类型, 是否为模块) 的 SHA-256：条目不保存源码，相同源码在不同模块标识下只转译一次
+type CacheKey = [u8; 32];
+
+fn cache_key(code: &str, media_type: MediaType, is_module: bool) -> CacheKey {
+    let mut hasher = Sha256::new();
+    hasher.update(media_type.as_ts_extension());
+    hasher.update([is_module as u8]);
+    hasher.update(code);
+    hasher.finalize().into()
+}
+
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<CacheKey, Rc<Transpiled>>,
+    order: VecDeque<CacheKey>,
+}
+
+// TS/JSX 转译器，转译结果按源码内容缓存
+#[derive(Default)]
+pub struct Transpiler {
+    cache: RefCell<Cache>,
+}
+
+impl Transpiler {
+    pub fn transpile(
+        &self,
+        specifier: &ModuleSpecifier,
+        code: &str,
+        media_type: MediaType,
+        is_module: bool,
+    ) -> Result<(String, Option<Vec<u8>>), String> {
+        let key = cache_key(code, media_type, is_module);
+
+        let cached = self.cache.borrow().entries.get(&key).cloned();
+        let transpiled = match cached {
+            Some(transpiled) => transpiled,
+            None => {
+                let transpiled = Rc::new(Self::emit(specifier, code, media_type, is_module)?);
+                let mut cache = self.cache.borrow_mut();
+                if cache.order.len() >= CACHE_CAPACITY {
+                    if let Some(oldest) = cache.order.pop_front() {
+                        cache.entries.remove(&oldest);
+                    }
+                }
+                cache.order.push_back(key);
+                cache.entries.insert(key, transpiled.clone());
+                transpiled
+            }
+        };
+        let source_map = match &transpiled.source_map {
+            Some(source_map) if transpiled.specifier != specifier.as_str() => Some(rename_sources(source_map, specifier)?),
+            source_map => source_map.clone(),
+        };
+        Ok((transpiled.code.clone(), source_map))
+    }
+
+    fn emit(
+        specifier: &ModuleSpecifier,
+        code: &str,
+        media_type: MediaType,
+        is_module: bool,
+    ) -> Result<Transpiled, String> {
+        let params = ParseParams {
+            specifier: specifier.clone(),
+            text: code.into(),
+            media_type,
+            capture_tokens: false,
+            scope_analysis: false,
+            maybe_syntax: None,
+        };
+        // 经典脚本不能包含 import/export，按脚本语法解析
+        let parsed = if is_module {
+            deno_ast::parse_module(params)
+        } else {
+            deno_ast::parse_script(params)
+        }
+        .map_err(|e| e.to_string())?;
+
+        let emitted = parsed
+            .transpile(
+                &TranspileOptions::default(),
+                &TranspileModuleOptions::default(),
+                &EmitOptions {
+                    source_map: SourceMapOption::Separate,
+                    ..Default::default()
+                },
+            )
+            .map_err(|e| e.to_string())?
+            .into_source();
+
+        Ok(Transpiled {
+            code: emitted.text,
+            source_map: emitted.source_map.map(String::into_bytes),
+            specifier: specifier.to_string(),
+        })
+    }
+}
+
+// 命中其他模块标识下缓存的结果时，将 source map 的 sources 改为当前模块
+fn rename_sources(source_map: &[u8], specifier: &ModuleSpecifier) -> Result<Vec<u8>, String> {
+    let mut map: serde_json::Value = serde_json::from_slice(source_map).map_err(|e| e.to_string())?;
+    if let Some(map) = map.as_object_mut() {
+        map.insert("sources".to_string(), serde_json::json!([specifier.as_str()]));
+    }
+    serde_json::to_vec(&map).map_err(|e| e.to_string())
+}
@@ -1,50 +1,146 @@
-use deno_core::{v8, FsModuleLoader, JsRuntime, RuntimeOptions, ModuleSpecifier};
+use deno_core::{serde_v8, v8, JsRuntime, RuntimeOptions, ModuleSpecifier, PollEventLoopOptions};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use pyo3::prelude::*;
 use parking_lot::RwLock;
-use pyo3::types::PyTuple;
+use pyo3::types::{PyTuple, PyType};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::rc::Rc;
-use pyo3::exceptions::{PyRuntimeError, PyKeyError};
+use pyo3::exceptions::{PyRuntimeError, PyKeyError, PyValueError};
 use crate::types::convert::{js_to_py, py_to_js};
+use crate::types::promise::RuntimeRef;
+use crate::types::proxy;
+use crate::types::json::{js_to_json, js_to_py_json, py_to_js_json};
 use crate::types::error::JsError;
+use crate::types::registry::{ConverterRegistry, JsConverter, PyConverter};
+use crate::types::options::{ConvertOptions, IntOverflow, MapMode, NumberMode, Transfer, TypedArrayMode};
+use crate::engine::virtual_loader::{virtual_fs, VirtualModuleLoader, REQUIRE_JS};
+use crate::engine::py_ops::{py_ops, PyOps};
+use crate::engine::py_modules::{py_modules, PythonModules};
+use crate::engine::py_async::{py_async, PyAwaitables, AWAIT_JS};
+use crate::engine::runtime_cell::RuntimeCell;
+use crate::engine::engine_thread::{EngineBound, ThreadBound};
+use crate::engine::process::RemoteContext;
+use std::future::Future;
 use std::path::Path;
 
+static MODULE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 // 标记为不可跨线程的Python类
 #[pyclass(unsendable)]
 #[derive(Clone)]
 pub struct JsEngine {
-    runtime: Arc<RwLock<JsRuntime>>,
+    runtime: Arc<RuntimeCell>,
+    loader: Rc<VirtualModuleLoader>,
+    converters: Rc<ConverterRegistry>,
+    python_modules: Rc<PythonModules>,
 }
 
-// 上下文结构体，持有引擎引用和函数缓存
-#[pyclass(unsendable)]
+// SAFETY: Rc/Arc 的引用计数只在引擎线程上增减，runtime 只在引擎线程上访问
+unsafe impl EngineBound for JsEngine {}
+
+// 上下文结构体，持有引擎引用和函数缓存；可在任意线程中使用，访问转交引擎所在线程执行
+#[pyclass]
 pub struct PyContext {
+    state: ContextHandle,
+}
+
+enum ContextHandle {
+    Local(ThreadBound<ContextState>),
+    // isolation="process" 时位于子进程中的上下文
+    Remote(RemoteContext),
+}
+
+struct ContextState {
     engine: Arc<JsEngine>,
     global_snapshot: RwLock<HashMap<String, v8::Global<v8::Value>>>,
 }
 
+// SAFETY: 同 JsEngine
+unsafe impl EngineBound for ContextState {}
+
 
 
 #[pymethods]
 impl JsEngine {
     #[new]
-    pub fn new() -> Self {
-        let mut options = RuntimeOptions::default();
-        options.module_loader = Some(Rc::new(FsModuleLoader));
-        Self {
-            runtime: Arc::new(RwLock::new(JsRuntime::new(options))),
+    #[pyo3(signature = (modules=None, module_loader=None, int_overflow="bigint", number_mode="auto", typed_array="auto", zero_copy=false, map_mode="dict", prototype_getters=false, ops=None, ops_namespace="host", max_nesting_depth=16))]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(_py: Python<'_>, modules: Option<HashMap<String, String>>, module_loader: Option<PyObject>, int_overflow: &str, number_mode: &str, typed_array: &str, zero_copy: bool, map_mode: &str, prototype_getters: bool, ops: Option<HashMap<String, PyObject>>, ops_namespace: &str, max_nesting_depth: usize) -> PyResult<Self> {
+        let convert_options = ConvertOptions {
+            int_overflow: IntOverflow::parse(int_overflow)?,
+            number_mode: NumberMode::parse(number_mode)?,
+            typed_array: TypedArrayMode::parse(typed_array)?,
+            zero_copy,
+            map_mode: MapMode::parse(map_mode)?,
+            prototype_getters,
+        };
+        let ops = ops.map(|ops| PyOps::new(ops, ops_namespace.to_string()));
+        Ok(Self::with_options(modules, module_loader, convert_options, ops, max_nesting_depth)?)
+    }
+
+    // 注册 Python 模块或 dict，JS 中通过 import ... from "python:<name>" 导入
+    pub fn register_python_module(&self, name: String, module: PyObject) {
+        self.python_modules.register(name, module);
+    }
+
+    // 注册 JS -> Python 的转换器，按构造函数名匹配
+    #[pyo3(signature = (constructor_name, to_py=None, js=None))]
+    pub fn register_js_converter(&self, constructor_name: String, to_py: Option<PyObject>, js: Option<String>) -> PyResult<()> {
+        if to_py.is_none() && js.is_none() {
+            return Err(PyValueError::new_err("Either to_py or js must be given"));
         }
+        let js = js.map(|source| self.compile_converter(&source)).transpose()?;
+        self.converters.register_js(constructor_name, JsConverter { js, to_py });
+        Ok(())
     }
 
-    pub fn eval(&self, py: Python<'_>, code: String) -> PyResult<PyObject> {
-        let mut runtime = self.runtime.write();
-        let result = runtime.execute_script("<eval>", code)
-            .map_err(|e| JsError::ExecutionError(e.to_string()))?;
-        
-        let scope = &mut runtime.handle_scope();
-        let local = v8::Local::new(scope, result);
-        js_to_py(py, scope, local)
+    // 注册 Python -> JS 的转换器，按 isinstance 匹配
+    #[pyo3(signature = (py_type, to_js=None, js=None))]
+    pub fn register_py_converter(&self, py_type: PyObject, to_js: Option<PyObject>, js: Option<String>) -> PyResult<()> {
+        if to_js.is_none() && js.is_none() {
+            return Err(PyValueError::new_err("Either to_js or js must be given"));
+        }
+        let js = js.map(|source| self.compile_converter(&source)).transpose()?;
+        self.converters.register_py(PyConverter { py_type, to_js, js });
+        Ok(())
+    }
+
+    // 将 Python 对象以代理形式挂到 JS 全局对象上，属性读写与方法调用实时转发到该对象
+    pub fn expose(&self, py: Python<'_>, name: String, obj: PyObject) -> PyResult<()> {
+        self.runtime.enter()?.with_scope(|scope| {
+            let value = proxy::to_js(scope, obj.bind(py))?;
+            let key = v8::String::new(scope, &name)
+                .ok_or_else(|| PyValueError::new_err(format!("Invalid name: {}", name)))?;
+            let global = scope.get_current_context().global(scope);
+            global.set(scope, key.into(), value)
+                .ok_or_else(|| PyRuntimeError::new_err(format!("Failed to expose {}", name)))?;
+            Ok(())
+        })
+    }
+
+    // 将 Python 类注册为 JS 全局构造函数，名称默认为类名
+    #[pyo3(signature = (class, name=None))]
+    pub fn register_class(&self, py: Python<'_>, class: Bound<'_, PyType>, name: Option<String>) -> PyResult<()> {
+        let name = match name {
+            Some(name) => name,
+            None => class.name()?.to_string(),
+        };
+        self.expose(py, name, class.into_any().unbind())
+    }
+
+    #[pyo3(signature = (code, filename=None))]
+    pub fn eval(&self, py: Python<'_>, code: String, filename: Option<String>) -> PyResult<PyObject> {
+        let result = self.run_script(&code, filename.as_deref().unwrap_or("<eval>"))?;
+        // 结果为 Promise（例如 import()）时驱动事件循环直至完成
+        let result = self.resolve_value(result)?;
+
+        self.runtime.enter()?.with_scope(|scope| {
+            let local = v8::Local::new(scope, result);
+            js_to_py(py, scope, local)
+        })
     }
 
 
@@ -53,141 +149,531 @@ impl JsEngine {
         let engine_arc = Arc::new(self.clone());
         // 分离异步操作和同步操作的作用域
         let module_ns = {
-            let mut runtime = self.runtime.write();
+            let mut guard = self.runtime.enter()?;
+            let runtime = guard.runtime()?;
             let absolute_path = std::fs::canonicalize(Path::new(&file_path))
                 .map_err(|e| PyRuntimeError::new_err(format!("Invalid path: {}", e)))?;
             let specifier = ModuleSpecifier::from_file_path(&absolute_path)
                 .map_err(|_| PyRuntimeError::new_err("Invalid file path"))?;
     
             // 异步加载模块
-            let (_module_id, ns) = futures::executor::block_on(async {
+            let (_module_id, ns) = block_on_without_gil(async {
                 let module_id = runtime.load_main_es_module(&specifier).await?;
                 let ns = runtime.get_module_namespace(module_id)?;
-                runtime.mod_evaluate(module_id).await?;
+                let evaluate = runtime.mod_evaluate(module_id);
+                runtime.with_event_loop_future(evaluate, PollEventLoopOptions::default()).await?;
                 Ok::<_, deno_core::anyhow::Error>((module_id, ns))
             }).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
     
             ns
         };
         
-        // 创建新的作用域收集全局对象
-        let global_snapshot = {
-            let mut runtime = self.runtime.write();
+        let global_snapshot = self.collect_exports(&module_ns)?;
+
+        Ok(PyContext::new(engine_arc, global_snapshot))
+    }
+
+    #[pyo3(signature = (code, specifier=None, base_dir=None))]
+    pub fn compile_module(&self, _py: Python<'_>, code: String, specifier: Option<String>, base_dir: Option<String>) -> PyResult<PyContext> {
+        let engine_arc = Arc::new(self.clone());
+        // 相对导入以 base_dir 为基准，默认使用当前工作目录
+        let base = match base_dir {
+            Some(dir) => std::fs::canonicalize(Path::new(&dir))
+                .map_err(|e| PyRuntimeError::new_err(format!("Invalid base dir: {}", e)))?,
+            None => std::env::current_dir()
+                .map_err(|e| PyRuntimeError::new_err(format!("Invalid current dir: {}", e)))?,
+        };
+        // 同一个 specifier 只能加载一次，未指定时自动生成
+        let specifier = specifier.unwrap_or_else(|| {
+            format!("__module_{}__.js", MODULE_COUNTER.fetch_add(1, Ordering::Relaxed))
+        });
+        let specifier = deno_core::resolve_url_or_path(&specifier, &base)
+            .map_err(|e| PyRuntimeError::new_err(format!("Invalid specifier: {}", e)))?;
+
+        let code = self.loader.prepare_source(&specifier, code, true)
+            .map_err(|e| JsError::ExecutionError(e.to_string()))?;
+
+        let module_ns = {
+            let mut guard = self.runtime.enter()?;
+            let runtime = guard.runtime()?;
+            block_on_without_gil(async {
+                let module_id = runtime.load_side_es_module_from_code(&specifier, code).await?;
+                let ns = runtime.get_module_namespace(module_id)?;
+                let evaluate = runtime.mod_evaluate(module_id);
+                runtime.with_event_loop_future(evaluate, PollEventLoopOptions::default()).await?;
+                Ok::<_, deno_core::anyhow::Error>(ns)
+            }).map_err(|e| PyRuntimeError::new_err(e.to_string()))?
+        };
+
+        let global_snapshot = self.collect_exports(&module_ns)?;
+
+        Ok(PyContext::new(engine_arc, global_snapshot))
+    }
+
+    #[pyo3(signature = (code, filename=None))]
+    pub fn compile_code(&self, _py: Python<'_>, code: String, filename: Option<String>) -> PyResult<PyContext> {
+        let engine_arc = Arc::new(self.clone());
+
+        // 编译并运行脚本，随后等待脚本中发起的动态 import() 完成
+        self.run_script(&code, filename.as_deref().unwrap_or("<anonymous>"))?;
+        self.run_event_loop()?;
+
+        // 收集函数
+        let global_snapshot = self.runtime.enter()?.with_scope(|scope| {
+            let global = scope.get_current_context().global(scope);
+            let mut global_snapshot  = HashMap::new();
+            let names = global
+                .get_property_names(scope, v8::GetPropertyNamesArgs::default())
+                .ok_or_else(|| PyRuntimeError::new_err("Failed to get property names"))?;
+            for i in 0..names.length() {
+                let key = names.get_index(scope, i).unwrap();
+                let key_str = key.to_rust_string_lossy(scope);
+                let value = global.get(scope, key).unwrap();
+                // 统一存储所有属性
+                global_snapshot.insert(key_str, v8::Global::new(scope, value));
+            }
+            Ok::<_, PyErr>(global_snapshot)
+        })?;
+        Ok(PyContext::new(engine_arc, global_snapshot))
+    }
+
+}
+
+impl JsEngine {
+    // 不经过 Python 参数解析的构造函数，供 Rust 调用方使用
+    pub fn with_options(
+        modules: Option<HashMap<String, String>>,
+        module_loader: Option<PyObject>,
+        convert_options: ConvertOptions,
+        ops: Option<PyOps>,
+        max_nesting_depth: usize,
+    ) -> Result<Self, JsError> {
+        let mut options = RuntimeOptions::default();
+        let virtual_fs = modules.is_some() || module_loader.is_some();
+        let python_modules = Rc::new(PythonModules::default());
+        let loader = Rc::new(VirtualModuleLoader::new(modules.unwrap_or_default(), module_loader, python_modules.clone()));
+        options.module_loader = Some(loader.clone());
+        options.extensions.push(py_modules::init_ops_and_esm(python_modules.clone()));
+        let awaitables = Rc::new(PyAwaitables::default());
+        options.extensions.push(py_async::init_ops_and_esm(awaitables.clone()));
+        if virtual_fs {
+            // 虚拟文件系统：require 通过扩展注入的 op 读取源码
+            options.extensions.push(virtual_fs::init_ops_and_esm(loader.clone()));
+        }
+        let ops = ops.map(Rc::new);
+        if let Some(ops) = &ops {
+            // Python 实现的 op 统一经 op_py_call/op_py_call_async 分发
+            options.extensions.push(py_ops::init_ops_and_esm(ops.clone()));
+        }
+        let mut runtime = JsRuntime::new(options);
+        if virtual_fs {
+            runtime.execute_script("ext:virtual_fs/require.js", REQUIRE_JS)
+                .map_err(|e| JsError::RuntimeError(e.to_string()))?;
+        }
+        if let Some(ops) = &ops {
+            runtime.execute_script("ext:py_ops/bootstrap.js", ops.bootstrap_script())
+                .map_err(|e| JsError::RuntimeError(e.to_string()))?;
+        }
+        // Python awaitable 经 JS 包装函数转为 Promise
+        let await_fn = runtime.execute_script("ext:py_async/await.js", AWAIT_JS)
+            .map_err(|e| JsError::RuntimeError(e.to_string()))?;
+        {
             let scope = &mut runtime.handle_scope();
-            
-            // 将v8::Global转换为Local
-            let local_ns = v8::Local::new(scope, &module_ns);
-            
+            let await_fn = v8::Local::new(scope, await_fn);
+            awaitables.install(scope, await_fn)?;
+        }
+        convert_options.install(runtime.v8_isolate());
+        let converters = Rc::new(ConverterRegistry::default());
+        converters.clone().install(runtime.v8_isolate());
+        let runtime = Arc::new(RuntimeCell::new(runtime, max_nesting_depth));
+        // 结果中嵌套的 Promise 转换为 JsPromise，经弱引用驱动事件循环
+        runtime.enter()?.runtime()?.v8_isolate().set_slot(RuntimeRef(Arc::downgrade(&runtime)));
+        Ok(Self {
+            runtime,
+            loader,
+            converters,
+            python_modules,
+        })
+    }
+
+    // 以文件 URL 作为脚本的资源名，动态 import() 据此解析相对路径
+    fn script_specifier(filename: &str) -> Result<ModuleSpecifier, JsError> {
+        let cwd = std::env::current_dir()
+            .map_err(|e| JsError::RuntimeError(format!("Invalid current dir: {}", e)))?;
+        deno_core::resolve_url_or_path(filename, &cwd)
+            .map_err(|e| JsError::RuntimeError(format!("Invalid filename: {}", e)))
+    }
+
+    // 编译并执行经典脚本，返回脚本的完成值
+    fn run_script(&self, code: &str, filename: &str) -> Result<v8::Global<v8::Value>, JsError> {
+        let specifier = Self::script_specifier(filename)?;
+        // .ts/.tsx/.jsx 文件名的脚本先转译，并记录 source map
+        let code = self.loader.prepare_source(&specifier, code.to_string(), false)
+            .map_err(|e| JsError::ExecutionError(e.to_string()))?;
+        self.runtime.enter()?.with_scope(|scope| {
+            let mut try_catch = v8::TryCatch::new(scope);
+            let scope = &mut try_catch;
+
+            let source = v8::String::new(scope, &code)
+                .ok_or_else(|| JsError::ExecutionError("Invalid code".to_string()))?;
+            let resource_name = v8::String::new(scope, specifier.as_str())
+                .ok_or_else(|| JsError::ExecutionError("Invalid filename".to_string()))?;
+            let origin = v8::ScriptOrigin::new(scope, resource_name.into(), 0, 0, false, 0, None, false, false, false, None);
+
+            // 执行 JS 期间释放 GIL，CPU 密集的脚本不会阻塞其他 Python 线程；回调 Python 时再各自获取
+            let result = v8::Script::compile(scope, source, Some(&origin))
+                .and_then(|script| without_gil(|| script.run(scope)));
+            match result {
+                Some(value) => Ok(v8::Global::new(scope, value)),
+                None => {
+                    let message = scope.exception()
+                        .map(|exception| exception_message(scope, exception))
+                        .unwrap_or_else(|| "Unknown JavaScript error".to_string());
+                    Err(JsError::ExecutionError(message))
+                }
+            }
+        })
+    }
+
+    // 转换器的 JS 源码须为函数表达式，例如 "v => v.toString()"
+    fn compile_converter(&self, source: &str) -> PyResult<v8::Global<v8::Function>> {
+        let value = self.run_script(&format!("({})", source), "<converter>")?;
+        self.runtime.enter()?.with_scope(|scope| {
+            let local = v8::Local::new(scope, value);
+            let func = v8::Local::<v8::Function>::try_from(local)
+                .map_err(|_| PyValueError::new_err(format!("Converter is not a function: {}", source)))?;
+            Ok(v8::Global::new(scope, func))
+        })
+    }
+
+    // 若值为 Promise，则驱动事件循环直到其完成；否则原样返回
+    pub(crate) fn resolve_value(&self, value: v8::Global<v8::Value>) -> Result<v8::Global<v8::Value>, JsError> {
+        let mut guard = self.runtime.enter()?;
+        // 嵌套调用不能驱动事件循环，只能取回已完成的 Promise
+        if guard.nested() {
+            return guard.with_scope(|scope| settled_value(scope, value));
+        }
+        let runtime = guard.runtime()?;
+        let resolve = runtime.resolve(value);
+        block_on_without_gil(runtime.with_event_loop_promise(resolve, PollEventLoopOptions::default()))
+            .map_err(|e| JsError::ExecutionError(e.to_string()))
+    }
+
+    // 运行事件循环直到没有待处理的任务（如动态导入）
+    // 嵌套调用时跳过，待处理的任务由外层调用驱动
+    fn run_event_loop(&self) -> PyResult<()> {
+        let mut guard = self.runtime.enter()?;
+        if guard.nested() {
+            return Ok(());
+        }
+        let runtime = guard.runtime()?;
+        block_on_without_gil(runtime.run_event_loop(PollEventLoopOptions::default()))
+            .map_err(|e| JsError::ExecutionError(e.to_string()).into())
+    }
+
+    // 收集模块命名空间中的全部导出（函数、常量、类以及 default）
+    fn collect_exports(&self, module_ns: &v8::Global<v8::Object>) -> PyResult<HashMap<String, v8::Global<v8::Value>>> {
+        self.runtime.enter()?.with_scope(|scope| {
+            let local_ns = v8::Local::new(scope, module_ns);
+
             let mut snapshot = HashMap::new();
             let names = local_ns.get_property_names(scope, v8::GetPropertyNamesArgs::default())
                 .ok_or_else(|| PyRuntimeError::new_err("Failed to get export names"))?;
-            // println!("names: {:?}", names);
             for i in 0..names.length() {
                 let key = names.get_index(scope, i).unwrap();
                 let key_str = key.to_rust_string_lossy(scope);
                 let value = local_ns.get(scope, key).unwrap();
-                // println!("key_str: {:?}", key_str);
-                // println!("value: {:?}", value);
-                // 只收集函数类型的导出
-                if value.is_function() {
-                    snapshot.insert(key_str, v8::Global::new(scope, value));
+                snapshot.insert(key_str, v8::Global::new(scope, value));
+            }
+            Ok(snapshot)
+        })
+    }
+}
+
+// 供 Rust 调用方使用的类型化接口，参数与返回值经 serde_v8 转换，不经过 PyO3
+impl JsEngine {
+    // 执行脚本并将结果（Promise 则为其完成值）反序列化为 R
+    pub fn eval_as<R: DeserializeOwned>(&self, code: &str) -> Result<R, JsError> {
+        let result = self.run_script(code, "<eval>")?;
+        let result = self.resolve_value(result)?;
+        self.deserialize(result)
+    }
+
+    // 调用全局函数（支持 "a.b.c" 形式的路径），args 为参数元组，每个元素作为一个参数，
+    // 例如 call::<_, i32>("add", (1, 2))；() 表示无参数，单个参数写作 (value,)
+    pub fn call<A: JsArgs, R: DeserializeOwned>(&self, name: &str, args: A) -> Result<R, JsError> {
+        let result = self.runtime.enter()?.with_scope(|scope| {
+            let mut try_catch = v8::TryCatch::new(scope);
+            let scope = &mut try_catch;
+
+            let mut this: v8::Local<v8::Value> = scope.get_current_context().global(scope).into();
+            let mut target = this;
+            for part in name.split('.') {
+                let object = target.to_object(scope)
+                    .ok_or_else(|| JsError::RuntimeError(format!("{} is not defined", name)))?;
+                let key = v8::String::new(scope, part)
+                    .ok_or_else(|| JsError::RuntimeError(format!("Invalid name: {}", name)))?;
+                this = object.into();
+                target = object.get(scope, key.into())
+                    .filter(|value| !value.is_undefined())
+                    .ok_or_else(|| JsError::RuntimeError(format!("{} is not defined", name)))?;
+            }
+            let func = v8::Local::<v8::Function>::try_from(target)
+                .map_err(|_| JsError::RuntimeError(format!("{} is not a function", name)))?;
+
+            let args = args.to_v8_args(scope)?;
+
+            match func.call(scope, this, &args) {
+                Some(result) => Ok(v8::Global::new(scope, result)),
+                None => {
+                    let message = scope.exception()
+                        .map(|exception| exception_message(scope, exception))
+                        .unwrap_or_else(|| "Failed to call function".to_string());
+                    Err(JsError::ExecutionError(message))
                 }
             }
-            snapshot
-        };
+        })?;
+        let result = self.resolve_value(result)?;
+        self.deserialize(result)
+    }
 
-        Ok(PyContext {
-            engine: engine_arc,
-            global_snapshot: RwLock::new(global_snapshot),
+    fn deserialize<R: DeserializeOwned>(&self, value: v8::Global<v8::Value>) -> Result<R, JsError> {
+        self.runtime.enter()?.with_scope(|scope| {
+            let local = v8::Local::new(scope, value);
+            serde_v8::from_v8(scope, local).map_err(|e| JsError::ConversionError(e.to_string()))
         })
     }
+}
 
-    #[pyo3(signature = (code))]
-    pub fn compile_code(&self, _py: Python<'_>, code: String) -> PyResult<PyContext> {
-        let engine_arc = Arc::new(self.clone());
+// JsEngine::call 的参数列表：由 Serialize 值组成的元组，每个元素经 serde_v8 转换为一个参数
+pub trait JsArgs {
+    fn to_v8_args<'s>(&self, scope: &mut v8::HandleScope<'s>) -> Result<Vec<v8::Local<'s, v8::Value>>, JsError>;
+}
 
-        // 获取可写的 runtime 引用
-        let mut runtime = self.runtime.write();
-        let scope = &mut runtime.handle_scope();
-        // 编译脚本
-        let source = v8::String::new(scope, &code)
-            .ok_or_else(|| JsError::ExecutionError("Invalid code".to_string()))?;
-        let script = v8::Script::compile(scope, source, None).ok_or_else(|| PyRuntimeError::new_err("Failed to compile script"))?;
-        script.run(scope);
-        // 收集函数
-        let global = scope.get_current_context().global(scope);
-        let mut global_snapshot  = HashMap::new();
-        let names = global
-            .get_property_names(scope, v8::GetPropertyNamesArgs::default())
-            .ok_or_else(|| PyRuntimeError::new_err("Failed to get property names"))?;
-        for i in 0..names.length() {
-            let key = names.get_index(scope, i).unwrap();
-            let key_str = key.to_rust_string_lossy(scope);
-            let value = global.get(scope, key).unwrap();
-            // 统一存储所有属性
-            global_snapshot.insert(key_str, v8::Global::new(scope, value));
-        }
-        Ok(PyContext {
-            engine: engine_arc,
-            global_snapshot: RwLock::new(global_snapshot),
+macro_rules! impl_js_args {
+    ($($arg:ident),*) => {
+        impl<$($arg: Serialize),*> JsArgs for ($($arg,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn to_v8_args<'s>(&self, scope: &mut v8::HandleScope<'s>) -> Result<Vec<v8::Local<'s, v8::Value>>, JsError> {
+                let ($($arg,)*) = self;
+                Ok(vec![$(
+                    serde_v8::to_v8(scope, $arg).map_err(|e| JsError::ConversionError(e.to_string()))?
+                ),*])
+            }
+        }
+    };
+}
+
+impl_js_args!();
+impl_js_args!(A);
+impl_js_args!(A, B);
+impl_js_args!(A, B, C);
+impl_js_args!(A, B, C, D);
+impl_js_args!(A, B, C, D, E);
+impl_js_args!(A, B, C, D, E, F);
+impl_js_args!(A, B, C, D, E, F, G);
+impl_js_args!(A, B, C, D, E, F, G, H);
+
+// 阻塞等待事件循环期间释放 GIL，工作线程中的异步 op 才能调用 Python
+pub(crate) fn block_on_without_gil<F: Future>(future: F) -> F::Output {
+    without_gil(|| futures::executor::block_on(future))
+}
+
+// allow_threads 要求闭包为 Send，但它会在当前线程上同步调用闭包，不会把闭包交给其他线程。
+// 本包装只用于 without_gil：闭包在创建它的线程上执行并丢弃，
+// 期间不直接访问 Python 对象（需要时经 Python::with_gil 重新获取 GIL）
+struct CurrentThreadJob<F: FnOnce()>(F);
+
+// SAFETY: 见上，值不会离开创建它的线程
+unsafe impl<F: FnOnce()> Send for CurrentThreadJob<F> {}
+
+impl<F: FnOnce()> CurrentThreadJob<F> {
+    fn run(self) {
+        (self.0)()
+    }
+}
+
+// 释放 GIL 执行 f；未初始化解释器或当前线程未持有 GIL（Rust 调用方）时直接执行
+pub(crate) fn without_gil<R>(f: impl FnOnce() -> R) -> R {
+    let holds_gil = unsafe { pyo3::ffi::Py_IsInitialized() != 0 && pyo3::ffi::PyGILState_Check() != 0 };
+    if !holds_gil {
+        return f();
+    }
+    // runtime 不能跨线程，结果经当前线程上的局部变量取回
+    let mut result = None;
+    let job = CurrentThreadJob(|| result = Some(f()));
+    Python::with_gil(|py| py.allow_threads(move || job.run()));
+    result.expect("allow_threads returned without running the closure")
+}
+
+// 不驱动事件循环地取回 Promise 的结果：未完成时报错，非 Promise 原样返回
+pub(crate) fn settled_value(scope: &mut v8::HandleScope, value: v8::Global<v8::Value>) -> Result<v8::Global<v8::Value>, JsError> {
+    let local = v8::Local::new(scope, &value);
+    let Ok(promise) = v8::Local::<v8::Promise>::try_from(local) else {
+        return Ok(value);
+    };
+    match promise.state() {
+        v8::PromiseState::Pending => Err(JsError::RuntimeError("Cannot wait for a pending Promise inside a nested call".to_string())),
+        v8::PromiseState::Fulfilled => {
+            let result = promise.result(scope);
+            Ok(v8::Global::new(scope, result))
+        }
+        v8::PromiseState::Rejected => {
+            // 添加空的 catch，拒绝已转为错误返回，不再作为未处理的拒绝报告
+            if let Some(noop) = v8::Function::new(scope, |_: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, _: v8::ReturnValue| {}) {
+                promise.catch(scope, noop);
+            }
+            let reason = promise.result(scope);
+            Err(JsError::ExecutionError(exception_message(scope, reason)))
+        }
+    }
+}
+
+// 通过 deno_core 格式化异常，堆栈会经 source map 映射回原始源码
+pub(crate) fn exception_message(scope: &mut v8::HandleScope, exception: v8::Local<v8::Value>) -> String {
+    deno_core::error::JsError::from_v8_exception(scope, exception).to_string()
+}
+
+// JSON 传输无法表示 Promise（JSON.stringify 得到 {}），未等待的 Promise 结果直接报错
+fn reject_promise(name: &str, value: v8::Local<v8::Value>) -> PyResult<()> {
+    if value.is_promise() {
+        return Err(PyRuntimeError::new_err(format!(
+            "{} returned a Promise; pass await_promise=True to wait for it",
+            name
+        )));
+    }
+    Ok(())
+}
+
+impl ContextState {
+    fn call_function(&self, py: Python<'_>, name: String, args: &Bound<'_, PyTuple>, transfer: Transfer, await_promise: bool) -> PyResult<PyObject> {
+        let result = self.invoke(&name, args, transfer, await_promise)?;
+        self.engine.runtime.enter()?.with_scope(|scope| {
+            let local = v8::Local::new(scope, result);
+            match transfer {
+                Transfer::Native => js_to_py(py, scope, local),
+                Transfer::Json => {
+                    reject_promise(&name, local)?;
+                    js_to_py_json(py, scope, local)
+                }
+            }
         })
     }
 
+    // 参数经 JSON 传入，返回值为 JSON.stringify 的原始字符串，由调用方自行解析
+    fn call_function_json(&self, name: String, args: &Bound<'_, PyTuple>, await_promise: bool) -> PyResult<String> {
+        let result = self.invoke(&name, args, Transfer::Json, await_promise)?;
+        self.engine.runtime.enter()?.with_scope(|scope| {
+            let local = v8::Local::new(scope, result);
+            reject_promise(&name, local)?;
+            js_to_json(scope, local)
+        })
+    }
+
+    fn get_property(&self, py: Python<'_>, expr: String) -> PyResult<PyObject> {
+        // 先取出值再进入 runtime，嵌套调用不会持有快照的锁
+        let value = self.global_snapshot.read().get(&expr).cloned()
+        .ok_or_else(|| PyKeyError::new_err(format!("Property {} not found", expr)))?;
+        self.engine.runtime.enter()?.with_scope(|scope| {
+            let local_value = v8::Local::new(scope, value);
+            js_to_py(py, scope, local_value)
+        })
+    }
+
+    // 调用快照中的函数；await_promise 为 true 且返回值为 Promise 时驱动事件循环等待其完成
+    fn invoke(&self, name: &str, args: &Bound<'_, PyTuple>, transfer: Transfer, await_promise: bool) -> PyResult<v8::Global<v8::Value>> {
+        let property = self.global_snapshot.read().get(name).cloned()
+            .ok_or_else(|| PyKeyError::new_err(format!("Property {} not found", name)))?;
+        let result = self.engine.runtime.enter()?.with_scope(|scope| {
+            let mut try_catch = v8::TryCatch::new(scope);
+            let scope = &mut try_catch;
+            let context = scope.get_current_context();
+            let this = {
+                let receiver_name = v8::String::new(scope, "this").ok_or_else(|| PyRuntimeError::new_err("Failed to create receiver name"))?;
+                context.global(scope).get(scope, receiver_name.into()).ok_or_else(|| PyRuntimeError::new_err("Failed to get this binding"))?
+            };
+            // let global = context.global(scope);
+            let local_func = match v8::Local::<v8::Function>::try_from(v8::Local::new(scope, &property)) {
+                Ok(f) if f.is_function() => f,
+                _ => return Err(PyRuntimeError::new_err(format!("{} is not a function", name))),
+            };
+            let mut v8_args = Vec::with_capacity(args.len());
+            match transfer {
+                Transfer::Native => {
+                    for item in args.iter() {
+                        v8_args.push(py_to_js(scope, &item)?);
+                    }
+                }
+                Transfer::Json => {
+                    // 全部参数作为一个数组序列化，只需一次 JSON.parse
+                    let array = v8::Local::<v8::Array>::try_from(py_to_js_json(scope, args.as_any())?)
+                        .map_err(|_| PyRuntimeError::new_err("Failed to convert arguments"))?;
+                    for i in 0..array.length() {
+                        v8_args.push(array.get_index(scope, i).unwrap());
+                    }
+                }
+            }
+            // println!("v8_args: {:?}", v8_args);
+            // 调用函数并处理错误，与 run_script 相同，执行期间释放 GIL
+            let result = match without_gil(|| local_func.call(scope, this.into(), &v8_args)) {
+                Some(result) => result,
+                None => {
+                    if let Some(exception) = scope.exception() {
+                        let message = exception_message(scope, exception);
+                        return Err(PyRuntimeError::new_err(message))
+                    } else {
+                        return Err(PyRuntimeError::new_err("Failed to call function"))
+                    }
+                }
+            };
+            Ok(v8::Global::new(scope, result))
+        })?;
+
+        // 默认原样返回 Promise（转换为可 await 的 JsPromise）；
+        // await_promise=True 时阻塞驱动事件循环，直到 Promise 完成（例如函数内部使用了 import()）
+        if !await_promise {
+            return Ok(result);
+        }
+        Ok(self.engine.resolve_value(result)?)
+    }
+}
+
+impl PyContext {
+    fn new(engine: Arc<JsEngine>, global_snapshot: HashMap<String, v8::Global<v8::Value>>) -> Self {
+        let state = ContextState { engine, global_snapshot: RwLock::new(global_snapshot) };
+        Self { state: ContextHandle::Local(ThreadBound::new(state)) }
+    }
+
+    pub(crate) fn remote(context: RemoteContext) -> Self {
+        Self { state: ContextHandle::Remote(context) }
+    }
 }
 
 #[pymethods]
 impl PyContext {
-    #[pyo3(signature = (name, *args))]
-    fn call_function(&self, py: Python<'_>, name: String, args: &Bound<'_, PyTuple>) -> PyResult<PyObject> { 
-        let global_snapshot = self.global_snapshot.read();
-        let property = global_snapshot.get(&name)
-            .ok_or_else(|| PyKeyError::new_err(format!("Property {} not found", name)))?;
-        let mut rt = self.engine.runtime.write();
-        let scope = &mut rt.handle_scope();
-        let mut try_catch = v8::TryCatch::new(&mut *scope);
-        let scope = &mut try_catch;
-        let context = scope.get_current_context();
-        let this = {
-            let receiver_name = v8::String::new(scope, "this").ok_or_else(|| PyRuntimeError::new_err("Failed to create receiver name"))?;
-            context.global(scope).get(scope, receiver_name.into()).ok_or_else(|| PyRuntimeError::new_err("Failed to get this binding"))?
-        };
-        // let global = context.global(scope);
-        let local_func = match v8::Local::<v8::Function>::try_from(v8::Local::new(scope, property)) {
-            Ok(f) if f.is_function() => f,
-            _ => return Err(PyRuntimeError::new_err(format!("{} is not a function", name))),
-        };
-        let mut v8_args = Vec::with_capacity(args.len());
-        for item in args.iter() {
-            v8_args.push(py_to_js(scope, &item)?);
-        }
-        // println!("v8_args: {:?}", v8_args);
-        // 调用函数并处理错误
-        let result = match local_func.call(scope, this.into(), &v8_args) {
-            Some(result) => result,
-            None => {
-                if let Some(exception) = scope.exception() {
-                    let message = exception.to_string(scope)
-                        .map(|msg| msg.to_rust_string_lossy(scope))
-                        .unwrap_or_else(|| "Unknown JavaScript error".to_string());
-                    return Err(PyRuntimeError::new_err(message))
-                } else {
-                    return Err(PyRuntimeError::new_err("Failed to call function"))
-                }
+    #[pyo3(signature = (name, *args, transfer="native", await_promise=false))]
+    pub(crate) fn call_function(&self, py: Python<'_>, name: String, args: Py<PyTuple>, transfer: &str, await_promise: bool) -> PyResult<PyObject> {
+        match &self.state {
+            ContextHandle::Local(state) => {
+                let transfer = Transfer::parse(transfer)?;
+                state.run(move |state| Python::with_gil(|py| state.call_function(py, name, args.bind(py), transfer, await_promise)))?
             }
-        };
-        if result.is_promise(){
-            return Err(PyRuntimeError::new_err("Promise not supported"))
+            ContextHandle::Remote(context) => context.call_function(py, name, args.bind(py), transfer, await_promise),
         }
-        js_to_py(py, scope, result)
     }
 
-    fn get_property(&self, py: Python<'_>, expr: String) -> PyResult<PyObject> {
-        let snapshot = self.global_snapshot.read();
-        let value = snapshot.get(&expr)
-        .ok_or_else(|| PyKeyError::new_err(format!("Property {} not found", expr)))?;
-        let mut rt = self.engine.runtime.write();
-        let scope = &mut rt.handle_scope();
-        let local_value = v8::Local::new(scope, value);
-        js_to_py(py, scope, local_value)
+    #[pyo3(signature = (name, *args, await_promise=false))]
+    pub(crate) fn call_function_json(&self, py: Python<'_>, name: String, args: Py<PyTuple>, await_promise: bool) -> PyResult<String> {
+        match &self.state {
+            ContextHandle::Local(state) => state.run(move |state| Python::with_gil(|py| state.call_function_json(name, args.bind(py), await_promise)))?,
+            ContextHandle::Remote(context) => context.call_function_json(name, args.bind(py), await_promise),
+        }
+    }
+
+    pub(crate) fn get_property(&self, py: Python<'_>, expr: String) -> PyResult<PyObject> {
+        match &self.state {
+            ContextHandle::Local(state) => state.run(move |state| Python::with_gil(|py| state.get_property(py, expr)))?,
+            ContextHandle::Remote(context) => context.get_property(py, expr),
+        }
     }
 }
@@ -0,0 +1,258 @@
+use deno_core::{
+    op2, FsModuleLoader, ModuleLoadResponse, ModuleLoader, ModuleSource, ModuleSourceCode,
+    ModuleSpecifier, ModuleType, OpState, RequestedModuleType, ResolutionKind,
+};
+use deno_core::error::ModuleLoaderError;
+use deno_error::JsErrorBox;
+use pyo3::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use crate::engine::transpile::{transpile_media_type, Transpiler};
+use crate::engine::source_map::SourceMapStore;
+use crate::engine::py_modules::{PythonModules, PYTHON_SCHEME};
+
+// 虚拟文件使用的 URL scheme，例如 virtual:///sig3.js
+pub const VIRTUAL_SCHEME: &str = "virtual";
+
+// 从内存映射或 Python 回调中提供模块源码的加载器，import 未命中时回退到文件系统；TS/JSX 在加载时转译
+// python: 模块由注册的 Python 模块生成
+pub struct VirtualModuleLoader {
+    modules: HashMap<String, String>,
+    loader: Option<PyObject>,
+    transpiler: Transpiler,
+    source_maps: SourceMapStore,
+    python_modules: Rc<PythonModules>,
+    fs_loader: FsModuleLoader,
+}
+
+impl VirtualModuleLoader {
+    pub fn new(modules: HashMap<String, String>, loader: Option<PyObject>, python_modules: Rc<PythonModules>) -> Self {
+        let modules = modules
+            .into_iter()
+            .map(|(path, code)| (normalize_path(&path), code))
+            .collect();
+        Self {
+            modules,
+            loader,
+            transpiler: Transpiler::default(),
+            source_maps: SourceMapStore::default(),
+            python_modules,
+            fs_loader: FsModuleLoader,
+        }
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.modules.contains_key(&normalize_path(path))
+    }
+
+    // require.js 给出的路径按虚拟文件 virtual:///<path> 读取
+    pub fn read_source(&self, path: &str) -> Result<Option<String>, String> {
+        let specifier = Self::virtual_specifier(path).map_err(|e| e.to_string())?;
+        self.read_specifier(&specifier)
+    }
+
+    // 虚拟文件先查内存映射；未命中时以完整的 specifier 字符串调用 Python 回调，回调返回 None 表示未找到
+    fn read_specifier(&self, specifier: &ModuleSpecifier) -> Result<Option<String>, String> {
+        if specifier.scheme() == VIRTUAL_SCHEME {
+            if let Some(code) = self.modules.get(&normalize_path(specifier.path())) {
+                return Ok(Some(code.clone()));
+            }
+        }
+        match &self.loader {
+            Some(loader) => Python::with_gil(|py| {
+                loader
+                    .call1(py, (specifier.as_str(),))
+                    .and_then(|code| code.extract::<Option<String>>(py))
+                    .map_err(|e| e.to_string())
+            }),
+            None => Ok(None),
+        }
+    }
+
+    // 读取虚拟文件、Python 回调或磁盘上的文件，用于查找外部 source map
+    fn read_any(&self, specifier: &ModuleSpecifier) -> Option<String> {
+        match self.read_specifier(specifier) {
+            Ok(Some(code)) => Some(code),
+            _ => specifier.to_file_path().ok().and_then(|path| std::fs::read_to_string(path).ok()),
+        }
+    }
+
+    // TS/JSX 源码先转译为 JavaScript，并记录对应的 source map
+    pub fn prepare_source(&self, specifier: &ModuleSpecifier, code: String, is_module: bool) -> io::Result<String> {
+        match transpile_media_type(specifier) {
+            Some(media_type) => {
+                let (code, source_map) = self.transpiler
+                    .transpile(specifier, &code, media_type, is_module)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Failed to transpile {}: {}", specifier, e)))?;
+                if let Some(source_map) = source_map {
+                    self.source_maps.insert(specifier.as_str(), source_map);
+                }
+                Ok(code)
+            }
+            None => {
+                self.source_maps.register_source(specifier, &code, |s| self.read_any(s));
+                Ok(code)
+            }
+        }
+    }
+
+    fn module_source(&self, specifier: &ModuleSpecifier, code: String) -> io::Result<ModuleSource> {
+        let code = self.prepare_source(specifier, code, true)?;
+        let module_type = if specifier.path().ends_with(".json") {
+            ModuleType::Json
+        } else {
+            ModuleType::JavaScript
+        };
+        Ok(ModuleSource::new(
+            module_type,
+            ModuleSourceCode::String(code.into()),
+            specifier,
+            None,
+        ))
+    }
+
+    fn virtual_specifier(path: &str) -> io::Result<ModuleSpecifier> {
+        ModuleSpecifier::parse(&format!("{}:///{}", VIRTUAL_SCHEME, normalize_path(path)))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
+    }
+}
+
+impl ModuleLoader for VirtualModuleLoader {
+    fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &str,
+        _kind: ResolutionKind,
+    ) -> Result<ModuleSpecifier, ModuleLoaderError> {
+        // 裸模块名直接命中虚拟文件
+        if is_bare(specifier) && (self.contains(specifier) || self.loader.is_some()) {
+            return Ok(Self::virtual_specifier(specifier)?);
+        }
+
+        let resolved = deno_core::resolve_import(specifier, referrer)?;
+        // 相对于工作目录的文件路径若存在同名虚拟文件，则优先使用虚拟文件，即使磁盘上存在同名文件
+        if resolved.scheme() == "file" {
+            if let (Ok(path), Ok(cwd)) = (resolved.to_file_path(), std::env::current_dir()) {
+                if let Ok(relative) = path.strip_prefix(&cwd) {
+                    let relative = relative.to_string_lossy();
+                    if self.contains(&relative) {
+                        return Ok(Self::virtual_specifier(&relative)?);
+                    }
+                }
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn load(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        maybe_referrer: Option<&ModuleSpecifier>,
+        is_dyn_import: bool,
+        requested_module_type: RequestedModuleType,
+    ) -> ModuleLoadResponse {
+        if module_specifier.scheme() == PYTHON_SCHEME {
+            return ModuleLoadResponse::Sync(match self.python_modules.module_source(module_specifier) {
+                Ok(Some(code)) => Ok(ModuleSource::new(
+                    ModuleType::JavaScript,
+                    ModuleSourceCode::String(code.into()),
+                    module_specifier,
+                    None,
+                )),
+                Ok(None) => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Python module not registered: {}", module_specifier),
+                ).into()),
+                Err(e) => Err(io::Error::other(
+                    format!("Failed to load module {}: {}", module_specifier, e),
+                ).into()),
+            });
+        }
+
+        let code = match self.read_specifier(module_specifier) {
+            Ok(Some(code)) => code,
+            Ok(None) if module_specifier.scheme() == VIRTUAL_SCHEME => {
+                return ModuleLoadResponse::Sync(Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Module not found: {}", module_specifier),
+                ).into()));
+            }
+            // 磁盘上的 TS/JSX 文件需要同步读取后转译
+            Ok(None) if transpile_media_type(module_specifier).is_some() => {
+                match module_specifier.to_file_path().map(std::fs::read_to_string) {
+                    Ok(Ok(code)) => code,
+                    Ok(Err(e)) => return ModuleLoadResponse::Sync(Err(e.into())),
+                    Err(_) => return ModuleLoadResponse::Sync(Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Provided module specifier \"{}\" is not a file URL.", module_specifier),
+                    ).into())),
+                }
+            }
+            Ok(None) => {
+                return self.fs_loader.load(module_specifier, maybe_referrer, is_dyn_import, requested_module_type);
+            }
+            Err(e) => {
+                return ModuleLoadResponse::Sync(Err(io::Error::other(
+                    format!("Failed to load module {}: {}", module_specifier, e),
+                ).into()));
+            }
+        };
+        ModuleLoadResponse::Sync(self.module_source(module_specifier, code).map_err(Into::into))
+    }
+
+    fn get_source_map(&self, file_name: &str) -> Option<Cow<'_, [u8]>> {
+        // 经 FsModuleLoader 加载的文件在首次需要时才读取 sourceMappingURL
+        if !self.source_maps.contains(file_name) {
+            let specifier = ModuleSpecifier::parse(file_name).ok()?;
+            let code = self.read_any(&specifier)?;
+            self.source_maps.register_source(&specifier, &code, |s| self.read_any(s));
+        }
+        self.source_maps.get(file_name).map(Cow::Owned)
+    }
+}
+
+// require.js 含非 ASCII 注释，不能作为扩展脚本打包，由引擎创建后单独执行
+pub const REQUIRE_JS: &str = include_str!("js/require.js");
+
+// 供 require.js 同步读取模块源码；本扩展只在配置了虚拟模块时注册，只从虚拟文件系统读取，不回退到磁盘
+#[op2]
+#[string]
+fn op_load_module(state: &mut OpState, #[string] path: String) -> Result<Option<String>, JsErrorBox> {
+    state.borrow::<Rc<VirtualModuleLoader>>()
+        .read_source(&path)
+        .map_err(|e| JsErrorBox::generic(format!("Failed to load module {}: {}", path, e)))
+}
+
+deno_core::extension!(
+    virtual_fs,
+    ops = [op_load_module],
+    options = { loader: Rc<VirtualModuleLoader> },
+    state = |state, options| {
+        state.put(options.loader);
+    },
+);
+
+fn is_bare(specifier: &str) -> bool {
+    !specifier.starts_with("./")
+        && !specifier.starts_with("../")
+        && !specifier.starts_with('/')
+        && ModuleSpecifier::parse(specifier).is_err()
+}
+
+// 去掉 "./"、折叠 ".."，得到映射中使用的规范路径
+fn normalize_path(path: &str) -> String {
+    let mut normalized = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Normal(part) => normalized.push(part),
+            _ => {}
+        }
+    }
+    normalized.to_string_lossy().replace('\\', "/")
+}
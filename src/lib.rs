@@ -1,12 +1,26 @@
 use pyo3::prelude::*;
-mod engine;
-mod types;
+pub mod engine;
+pub mod types;
 mod python;
 
+// Rust 调用方直接使用的引擎与转换选项
+pub use engine::v8engine::{JsArgs, JsEngine};
+pub use types::options::ConvertOptions;
+
 /// A Python module implemented in Rust.
 #[pymodule]
-fn py_js_runtime(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn py_js_runtime(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<python::class::JsRuntime>()?;
+    m.add_class::<types::buffer::JsBuffer>()?;
+    m.add_class::<types::map::JsMap>()?;
+    m.add_class::<types::symbol::JsSymbol>()?;
+    m.add_class::<types::promise::JsPromise>()?;
+    m.add_class::<types::iterator::JsIterator>()?;
+    m.add_class::<types::iterator::JsAsyncIterator>()?;
+    m.add("JsException", py.get_type::<types::error::JsException>())?;
+    m.add("JsWorkerError", py.get_type::<types::error::JsWorkerError>())?;
+    // isolation="process" 的子进程入口
+    m.add_function(wrap_pyfunction!(engine::process::serve_worker, m)?)?;
     // m.add_class::<JsExecutor>()?;
     Ok(())
 }
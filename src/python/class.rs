@@ -1,7 +1,18 @@
 use pyo3::prelude::*;
+use std::sync::Arc;
+use crate::engine::engine_thread::ThreadBound;
+use crate::engine::process::ProcessRuntime;
 use crate::engine::v8engine::JsEngine;
 
-#[pyclass(name = "JsRuntime", unsendable)]
+// 引擎运行在专用线程上，任意 Python 线程都可以调用，等待期间释放 GIL
+#[pyclass(name = "JsRuntime")]
 pub struct JsRuntime {
-    pub engine: Py<JsEngine>,
-}
\ No newline at end of file
+    pub backend: Backend,
+}
+
+pub enum Backend {
+    // isolation="thread"：当前进程中的专用线程
+    Thread(ThreadBound<JsEngine>),
+    // isolation="process"：子进程，崩溃不影响当前进程
+    Process(Arc<ProcessRuntime>),
+}
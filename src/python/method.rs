@@ -1,32 +1,136 @@
 use pyo3::prelude::*;
-use super::class::JsRuntime;
+use pyo3::exceptions::{PyNotImplementedError, PyValueError};
+use pyo3::types::PyType;
+use std::collections::HashMap;
+use std::time::Duration;
+use super::class::{Backend, JsRuntime};
+use crate::engine::engine_thread::{EngineThread, ThreadBound};
+use crate::engine::process::ProcessRuntime;
 use crate::engine::v8engine::{PyContext, JsEngine};
+use crate::types::wire::Value;
 
+// isolation="thread" 时每个方法都在引擎线程上执行：参数以 Py 引用传过去，在该线程上重新获取 GIL 后调用 JsEngine
+// JsEngine 只在转换值时持有 GIL，执行 JS 与驱动事件循环期间释放 GIL
+// isolation="process" 时参数与结果按值序列化，经管道发给子进程
 #[pymethods]
 impl JsRuntime {
     #[new]
-    fn new(py: Python) -> PyResult<Self> {
-        Ok(Self {
-            // 创建Python对象而不是纯Rust对象
-            engine: Py::new(py, JsEngine::new())?,
-        })
+    #[pyo3(signature = (modules=None, module_loader=None, int_overflow="bigint", number_mode="auto", typed_array="auto", zero_copy=false, map_mode="dict", prototype_getters=false, ops=None, ops_namespace="host", max_nesting_depth=16, isolation="thread", worker_timeout=Some(60.0)))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(py: Python<'_>, modules: Option<HashMap<String, String>>, module_loader: Option<PyObject>, int_overflow: &str, number_mode: &str, typed_array: &str, zero_copy: bool, map_mode: &str, prototype_getters: bool, ops: Option<HashMap<String, PyObject>>, ops_namespace: &str, max_nesting_depth: usize, isolation: &str, worker_timeout: Option<f64>) -> PyResult<Self> {
+        let backend = match isolation {
+            "thread" => {
+                let (int_overflow, number_mode, typed_array) = (int_overflow.to_string(), number_mode.to_string(), typed_array.to_string());
+                let (map_mode, ops_namespace) = (map_mode.to_string(), ops_namespace.to_string());
+                Backend::Thread(EngineThread::spawn(move || {
+                    Python::with_gil(|py| JsEngine::new(py, modules, module_loader, &int_overflow, &number_mode, &typed_array, zero_copy, &map_mode, prototype_getters, ops, &ops_namespace, max_nesting_depth))
+                })?)
+            }
+            "process" => {
+                // 子进程中无法调用当前进程的 Python 对象
+                if module_loader.is_some() || ops.is_some() {
+                    return Err(PyValueError::new_err("module_loader and ops are not supported with isolation=\"process\""));
+                }
+                let modules = modules.map(|modules| {
+                    Value::Dict(modules.into_iter().map(|(name, source)| (Value::Str(name), Value::Str(source))).collect())
+                });
+                let options = [
+                    ("modules", modules.unwrap_or(Value::None)),
+                    ("int_overflow", Value::str(int_overflow)),
+                    ("number_mode", Value::str(number_mode)),
+                    ("typed_array", Value::str(typed_array)),
+                    ("zero_copy", Value::Bool(zero_copy)),
+                    ("map_mode", Value::str(map_mode)),
+                    ("prototype_getters", Value::Bool(prototype_getters)),
+                    ("max_nesting_depth", Value::Int(max_nesting_depth as i64)),
+                ];
+                let options = Value::Dict(options.into_iter().map(|(name, value)| (Value::str(name), value)).collect());
+                // 子进程单个请求的响应期限（秒），超时后结束并在下次调用时重启；None 表示不限时
+                let timeout = worker_timeout
+                    .map(|seconds| Duration::try_from_secs_f64(seconds).ok().filter(|timeout| !timeout.is_zero())
+                        .ok_or_else(|| PyValueError::new_err(format!("Invalid worker_timeout {}, expected a positive number of seconds", seconds))))
+                    .transpose()?;
+                Backend::Process(ProcessRuntime::new(py, options, timeout)?)
+            }
+            _ => return Err(PyValueError::new_err(format!(
+                "Invalid isolation {:?}, expected \"thread\" or \"process\"",
+                isolation
+            ))),
+        };
+        Ok(Self { backend })
     }
 
-    fn eval(&self, py: Python<'_>, code: String) -> PyResult<PyObject> {
-        // 通过Python对象调用方法
-        self.engine.borrow(py).eval(py, code)
+    #[pyo3(signature = (code, filename=None))]
+    fn eval(&self, py: Python<'_>, code: String, filename: Option<String>) -> PyResult<PyObject> {
+        match &self.backend {
+            Backend::Thread(engine) => engine.run(move |engine| Python::with_gil(|py| engine.eval(py, code, filename)))?,
+            Backend::Process(runtime) => runtime.eval(py, code, filename),
+        }
     }
 
-    fn compile_file(&self, py: Python<'_>, file_path: String) -> PyResult<PyContext> {
-        let engine_ref = self.engine.borrow(py);
-        engine_ref.compile_file(py, file_path)
+    fn compile_file(&self, file_path: String) -> PyResult<PyContext> {
+        match &self.backend {
+            Backend::Thread(engine) => engine.run(move |engine| Python::with_gil(|py| engine.compile_file(py, file_path)))?,
+            Backend::Process(runtime) => runtime.compile_file(file_path),
+        }
     }
 
-    fn compile_code(&self, py: Python<'_>, code: String) -> PyResult<PyContext> {
-        let engine_ref = self.engine.borrow(py);
-        engine_ref.compile_code(py, code)
+    #[pyo3(signature = (code, specifier=None, base_dir=None))]
+    fn compile_module(&self, code: String, specifier: Option<String>, base_dir: Option<String>) -> PyResult<PyContext> {
+        match &self.backend {
+            Backend::Thread(engine) => engine.run(move |engine| Python::with_gil(|py| engine.compile_module(py, code, specifier, base_dir)))?,
+            Backend::Process(runtime) => runtime.compile_module(code, specifier, base_dir),
+        }
     }
-    
 
+    fn register_python_module(&self, name: String, module: PyObject) -> PyResult<()> {
+        self.engine("register_python_module")?.run(move |engine| engine.register_python_module(name, module))
+    }
+
+    fn expose(&self, name: String, obj: PyObject) -> PyResult<()> {
+        self.engine("expose")?.run(move |engine| Python::with_gil(|py| engine.expose(py, name, obj)))?
+    }
+
+    #[pyo3(signature = (class, name=None))]
+    fn register_class(&self, class: Py<PyType>, name: Option<String>) -> PyResult<()> {
+        self.engine("register_class")?.run(move |engine| Python::with_gil(|py| engine.register_class(py, class.into_bound(py), name)))?
+    }
+
+    #[pyo3(signature = (constructor_name, to_py=None, js=None))]
+    fn register_js_converter(&self, constructor_name: String, to_py: Option<PyObject>, js: Option<String>) -> PyResult<()> {
+        match (&self.backend, to_py, js) {
+            (Backend::Thread(engine), to_py, js) => engine.run(move |engine| engine.register_js_converter(constructor_name, to_py, js))?,
+            // 子进程中只能使用 JS 实现的转换器
+            (Backend::Process(runtime), None, Some(js)) => runtime.register_js_converter(constructor_name, js),
+            (Backend::Process(_), _, _) => Err(PyNotImplementedError::new_err(
+                "register_js_converter with to_py is not supported with isolation=\"process\"",
+            )),
+        }
+    }
 
-}
\ No newline at end of file
+    #[pyo3(signature = (py_type, to_js=None, js=None))]
+    fn register_py_converter(&self, py_type: PyObject, to_js: Option<PyObject>, js: Option<String>) -> PyResult<()> {
+        self.engine("register_py_converter")?.run(move |engine| engine.register_py_converter(py_type, to_js, js))?
+    }
+
+    #[pyo3(signature = (code, filename=None))]
+    fn compile_code(&self, code: String, filename: Option<String>) -> PyResult<PyContext> {
+        match &self.backend {
+            Backend::Thread(engine) => engine.run(move |engine| Python::with_gil(|py| engine.compile_code(py, code, filename)))?,
+            Backend::Process(runtime) => runtime.compile_code(code, filename),
+        }
+    }
+}
+
+impl JsRuntime {
+    // 需要在 JS 中引用当前进程 Python 对象的方法只支持 isolation="thread"
+    fn engine(&self, method: &str) -> PyResult<&ThreadBound<JsEngine>> {
+        match &self.backend {
+            Backend::Thread(engine) => Ok(engine),
+            Backend::Process(_) => Err(PyNotImplementedError::new_err(format!(
+                "{} is not supported with isolation=\"process\"",
+                method
+            ))),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use pyo3::ffi;
+use pyo3::exceptions::PyBufferError;
+use std::ffi::{c_int, c_void, CStr};
+use std::mem::MaybeUninit;
+use std::ptr::{self, NonNull};
+use std::sync::Mutex;
+
+// 直接引用 V8 backing store 的缓冲区对象，通过 buffer 协议零拷贝暴露给 Python
+#[pyclass(name = "JsBuffer")]
+pub struct JsBuffer {
+    store: SharedStore,
+    offset: usize,
+    len: usize,
+    format: &'static CStr,
+    item_size: usize,
+    // buffer 协议要求 shape/strides 在导出期间保持有效
+    shape: [ffi::Py_ssize_t; 1],
+    strides: [ffi::Py_ssize_t; 1],
+}
+
+// 可跨线程持有的 backing store 引用，只提供数据地址。
+// v8::BackingStore 为 Send 而非 Sync：这里只读取创建后不再改变的数据地址，
+// shared_ptr 的引用计数为原子操作，最后一个引用可在任意线程释放
+// （V8 的分配器线程安全，Python 缓冲区的 deleter 经 RELEASED 队列转交持有 GIL 的线程）
+struct SharedStore(v8::SharedRef<v8::BackingStore>);
+
+// SAFETY: 见上；不暴露 BackingStore 的其他方法
+unsafe impl Send for SharedStore {}
+unsafe impl Sync for SharedStore {}
+
+impl SharedStore {
+    fn data(&self) -> Option<NonNull<c_void>> {
+        self.0.data()
+    }
+}
+
+impl JsBuffer {
+    pub fn new(
+        store: v8::SharedRef<v8::BackingStore>,
+        offset: usize,
+        len: usize,
+        format: &'static CStr,
+        item_size: usize,
+    ) -> Self {
+        Self {
+            store: SharedStore(store),
+            offset,
+            len,
+            format,
+            item_size,
+            shape: [(len / item_size) as ffi::Py_ssize_t],
+            strides: [item_size as ffi::Py_ssize_t],
+        }
+    }
+}
+
+#[pymethods]
+impl JsBuffer {
+    unsafe fn __getbuffer__(slf: Bound<'_, Self>, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        // backing store 来自 V8 或可写的 Python 缓冲区（只读缓冲区不共享），导出始终可写
+        let this = slf.borrow();
+        let data = this.store.data()
+            .map(|data| data.as_ptr() as *mut u8)
+            .unwrap_or(NonNull::dangling().as_ptr())
+            .add(this.offset);
+
+        (*view).obj = slf.clone().into_any().into_ptr();
+        (*view).buf = data as *mut c_void;
+        (*view).len = this.len as ffi::Py_ssize_t;
+        (*view).readonly = 0;
+        (*view).itemsize = this.item_size as ffi::Py_ssize_t;
+        (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            this.format.as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        (*view).ndim = 1;
+        (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+            this.shape.as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            this.strides.as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        (*view).suboffsets = ptr::null_mut();
+        (*view).internal = ptr::null_mut();
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+
+    fn __len__(&self) -> usize {
+        self.len / self.item_size
+    }
+}
+
+// 被 V8 ArrayBuffer 引用的 Python 缓冲区
+struct PinnedBuffer(Box<ffi::Py_buffer>);
+
+// Py_buffer 只在持有 GIL 时释放
+unsafe impl Send for PinnedBuffer {}
+
+// V8 回收 ArrayBuffer 后等待释放的缓冲区
+static RELEASED: Mutex<Vec<PinnedBuffer>> = Mutex::new(Vec::new());
+
+// deleter 可能在没有 GIL 的线程上调用，先放入队列，由 release_pending 在持有 GIL 时释放
+unsafe extern "C" fn release_buffer(_data: *mut c_void, _byte_length: usize, deleter_data: *mut c_void) {
+    let pinned = Box::from_raw(deleter_data as *mut PinnedBuffer);
+    RELEASED.lock().unwrap().push(*pinned);
+}
+
+// 释放 V8 已回收的 Python 缓冲区导出。每次进入与退出 runtime、销毁 runtime 时调用，
+// 使源对象（如 bytearray）在 ArrayBuffer 被回收后尽快解除锁定
+pub fn release_pending() {
+    if RELEASED.lock().unwrap().is_empty() {
+        return;
+    }
+    Python::with_gil(|_py| {
+        let pending = std::mem::take(&mut *RELEASED.lock().unwrap());
+        for mut buffer in pending {
+            unsafe { ffi::PyBuffer_Release(&mut *buffer.0) };
+        }
+    });
+}
+
+// 以可写、C 连续的 Python 缓冲区内存创建 backing store，不满足条件时返回 None 由调用方拷贝。
+// V8 的 ArrayBuffer 无法设为只读，bytes 等只读缓冲区总是拷贝，避免 JS 修改不可变对象
+pub fn shared_backing_store(obj: &Bound<'_, PyAny>) -> Option<v8::SharedRef<v8::BackingStore>> {
+    release_pending();
+
+    let mut view: Box<ffi::Py_buffer> = unsafe { Box::new(MaybeUninit::zeroed().assume_init()) };
+    let flags = ffi::PyBUF_WRITABLE | ffi::PyBUF_C_CONTIGUOUS;
+    if unsafe { ffi::PyObject_GetBuffer(obj.as_ptr(), &mut *view, flags) } != 0 {
+        unsafe { ffi::PyErr_Clear() };
+        return None;
+    }
+    if view.len == 0 || view.buf.is_null() {
+        unsafe { ffi::PyBuffer_Release(&mut *view) };
+        return None;
+    }
+
+    let data = view.buf;
+    let len = view.len as usize;
+    let pinned = Box::into_raw(Box::new(PinnedBuffer(view)));
+    let store = unsafe {
+        v8::ArrayBuffer::new_backing_store_from_ptr(data, len, release_buffer, pinned as *mut c_void)
+    };
+    Some(store.make_shared())
+}
@@ -1,9 +1,28 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::PyTypeError;
-use pyo3::types::{PyString, PyFloat, PyDict, PyList, PyDateTime, PyBytes, PySet};
+use pyo3::exceptions::{PyBaseException, PyTypeError};
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyString, PyFloat, PyDict, PyList, PyDateTime, PyDate, PyTime, PyBytes, PySet, PyFrozenSet, PyTuple, PyType, PyInt, PyBool, PyByteArray, PyMemoryView};
 use deno_core::v8;
 use std::convert::TryFrom;
-use crate::types::error::TypeConversionError;
+use std::ffi::CStr;
+use crate::engine::py_async;
+use crate::engine::v8engine::exception_message;
+use crate::types::buffer::{shared_backing_store, JsBuffer};
+use crate::types::error::{JsException, TypeConversionError};
+use crate::types::iterator::{handle_iterable, iterator_local, py_iterable_to_js};
+use crate::types::map::JsMap;
+use crate::types::promise::JsPromise;
+use crate::types::proxy;
+use crate::types::symbol::JsSymbol;
+use crate::types::registry::{call_converter, ConverterRegistry};
+use crate::types::options::{ConvertOptions, IntOverflow, MapMode, NumberMode, TypedArrayMode};
+
+// JS Number 能精确表示的最大整数 2^53 - 1
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+// 1970-01-01 的 date.toordinal()
+pub(crate) const UNIX_EPOCH_ORDINAL: i64 = 719_163;
+pub(crate) const MS_PER_DAY: i64 = 86_400_000;
 
 // 简化 ValueExt trait
 trait ValueExt {
@@ -69,18 +88,60 @@ impl ValueExt for v8::Local<'_, v8::Value> {
     }
 }
 
-#[allow(deprecated)]
 pub fn js_to_py<'a>(
     py: Python<'_>,
     scope: &mut v8::HandleScope<'a>,
     value: v8::Local<'a, v8::Value>,
+) -> PyResult<PyObject> {
+    // Python 对象的代理直接取回原对象
+    if let Some(obj) = proxy::unwrap(py, scope, value) {
+        return Ok(obj);
+    }
+    // JS Proxy 按其 target 转换，不经过 get/ownKeys 等 trap
+    if value.is_proxy() {
+        return handle_proxy(py, scope, value);
+    }
+    // 优先使用按构造函数名注册的转换器
+    let converter = ConverterRegistry::get(scope).and_then(|registry| registry.find_js(scope, value));
+    let Some(converter) = converter else {
+        return js_to_py_builtin(py, scope, value);
+    };
+
+    let converted = match &converter.js {
+        Some(func) => {
+            let result = call_converter(scope, func, value)?;
+            // 返回对象本身时不再查找转换器，避免无限递归
+            if result.strict_equals(value) {
+                js_to_py_builtin(py, scope, result)?
+            } else {
+                js_to_py(py, scope, result)?
+            }
+        }
+        None => js_to_py_builtin(py, scope, value)?,
+    };
+    match &converter.to_py {
+        Some(to_py) => to_py.call1(py, (converted,)),
+        None => Ok(converted),
+    }
+}
+
+#[allow(deprecated)]
+fn js_to_py_builtin<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    value: v8::Local<'a, v8::Value>,
 ) -> PyResult<PyObject> {
     if value.is_null_or_undefined() {
         return Ok(py.None());
     }
 
+    // 包装对象（new Number(1) 等）按其原始值转换
+    if let Some(primitive) = unbox_primitive(scope, value) {
+        return js_to_py(py, scope, primitive);
+    }
+
     // 字符串处理
-    if value.is_string_object() || value.is_string() {
+    if value.is_string() {
         if let Some(s) = value.to_rust_string_lossy_if_string(scope) {
             return Ok(PyString::new(py, &s).into());
         }
@@ -94,18 +155,18 @@ pub fn js_to_py<'a>(
         if num.is_infinite() {
             return Ok((if num.is_sign_positive() { f64::INFINITY } else { f64::NEG_INFINITY }).into_pyobject(py)?.into());
         }
-        return handle_number(py, num);
+        return handle_number(py, num, value.is_int32(), ConvertOptions::get(scope));
     }
 
     // 布尔处理
-    if value.is_boolean_object() || value.is_boolean() {
+    if value.is_boolean() {
         let bool = value.boolean_value(scope);
         return Ok(bool.to_object(py));
     }
 
     // BigInt 处理
     if let Some(bigint) = value.as_bigint(scope) {
-        return Ok(bigint.i64_value().0.into_pyobject(py)?.into());
+        return handle_bigint(py, bigint);
     }
 
     // 日期处理
@@ -142,20 +203,36 @@ pub fn js_to_py<'a>(
     //     return handle_function(py, scope, value);
     // }
 
-    // Promise 处理
-    // if value.is_promise() {
-    //     return handle_promise(py, scope, value);
-    // }
+    // Promise 转为可 await 的 JsPromise
+    if value.is_promise() {
+        return handle_promise(py, scope, value);
+    }
+
+    // Symbol 处理
+    if value.is_symbol() {
+        return handle_symbol(py, scope, value);
+    }
+
+    // 正则表达式处理
+    if value.is_reg_exp() {
+        return handle_regexp(py, scope, value);
+    }
 
-    // 正则表达式处理 未实现
+    // Error 对象处理
+    if value.is_native_error() {
+        return handle_error(py, scope, value);
+    }
 
     // ArrayBuffer/ArrayBufferView 处理
     if value.is_array_buffer() || value.is_array_buffer_view() {
         return handle_array_buffer(py, scope, value);
     }
 
-    // 普通对象处理
+    // 普通对象处理；生成器等实现了迭代协议的对象转为惰性的 Python 迭代器
     if let Some(obj) = value.as_object(scope) {
+        if let Some(iterator) = handle_iterable(py, scope, obj)? {
+            return Ok(iterator);
+        }
         return handle_object(py, scope, obj);
     }
 
@@ -166,14 +243,45 @@ pub fn js_to_py<'a>(
 }
 
 #[inline]
-fn handle_number(py: Python<'_>, num: f64) -> PyResult<PyObject> {
-    if num.fract() == 0.0 && num <= i64::MAX as f64 && num >= i64::MIN as f64 {
+fn handle_number(py: Python<'_>, num: f64, is_int32: bool, options: ConvertOptions) -> PyResult<PyObject> {
+    let as_int = match options.number_mode {
+        // 超出安全整数范围的整数值可能已在 JS 中被舍入，不作为精确的 int 返回：
+        // int_overflow="raise" 时报错，否则保持为 float
+        NumberMode::Auto if num.fract() == 0.0 && num.abs() > MAX_SAFE_INTEGER as f64 => {
+            if options.int_overflow == IntOverflow::Raise {
+                return Err(TypeConversionError::InvalidValue(format!(
+                    "Number {} is outside the JS safe integer range and may have lost precision",
+                    num
+                )).into());
+            }
+            false
+        }
+        NumberMode::Auto => num.fract() == 0.0,
+        NumberMode::Int32 => is_int32,
+        NumberMode::Float => false,
+    };
+    if as_int {
         Ok((num as i64).into_pyobject(py)?.into())
     } else {
         Ok(PyFloat::new(py, num).into())
     }
 }
 
+#[inline]
+fn handle_bigint(py: Python<'_>, bigint: v8::Local<v8::BigInt>) -> PyResult<PyObject> {
+    // 按 64 位字完整读取，再以小端字节序构造 Python int，避免截断
+    let mut words = vec![0u64; bigint.word_count()];
+    let (negative, words) = bigint.to_words_array(&mut words);
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let value = py.get_type::<PyInt>()
+        .call_method1("from_bytes", (PyBytes::new(py, &bytes), "little"))?;
+    if negative {
+        Ok(value.neg()?.into())
+    } else {
+        Ok(value.into())
+    }
+}
+
 #[inline]
 fn handle_date(py: Python<'_>, date: v8::Local<v8::Date>) -> PyResult<PyObject> {
     let timestamp = date.value_of() / 1000.0;
@@ -195,6 +303,242 @@ fn handle_array(
     Ok(py_list.into())
 }
 
+// 通过 valueOf 取出包装对象的原始值
+fn unbox_primitive<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    value: v8::Local<'a, v8::Value>,
+) -> Option<v8::Local<'a, v8::Value>> {
+    if !(value.is_boolean_object()
+        || value.is_number_object()
+        || value.is_string_object()
+        || value.is_big_int_object()
+        || value.is_symbol_object())
+    {
+        return None;
+    }
+    let obj = value.to_object(scope)?;
+    let key = v8::String::new(scope, "valueOf")?;
+    let value_of = v8::Local::<v8::Function>::try_from(obj.get(scope, key.into())?).ok()?;
+    value_of.call(scope, obj.into(), &[])
+}
+
+fn handle_proxy<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    value: v8::Local<'a, v8::Value>,
+) -> PyResult<PyObject> {
+    let proxy = v8::Local::<v8::Proxy>::try_from(value)
+        .map_err(|_| TypeConversionError::InvalidValue("Failed to convert to Proxy".to_string()))?;
+    if proxy.is_revoked() {
+        return Err(TypeConversionError::InvalidValue("Cannot convert a revoked Proxy".to_string()).into());
+    }
+    let target = proxy.get_target(scope);
+    js_to_py(py, scope, target)
+}
+
+fn handle_symbol(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+) -> PyResult<PyObject> {
+    let symbol = v8::Local::<v8::Symbol>::try_from(value)
+        .map_err(|_| TypeConversionError::InvalidValue("Failed to convert to Symbol".to_string()))?;
+    let description = symbol.description(scope);
+    let description = (!description.is_undefined()).then(|| description.to_rust_string_lossy(scope));
+    Ok(Py::new(py, JsSymbol::new(description))?.into_any())
+}
+
+fn handle_regexp(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+) -> PyResult<PyObject> {
+    let regexp = v8::Local::<v8::RegExp>::try_from(value)
+        .map_err(|_| TypeConversionError::InvalidValue("Failed to convert to RegExp".to_string()))?;
+    let source = regexp.get_source(scope).to_rust_string_lossy(scope);
+    let flags = get_property(scope, regexp.into(), "flags")
+        .map(|flags| flags.to_rust_string_lossy(scope))
+        .unwrap_or_default();
+
+    // g/y/d 只影响 JS 的匹配状态，Python 中没有对应标志
+    let re = py.import("re")?;
+    let mut py_flags = 0;
+    for (flag, name) in [('i', "IGNORECASE"), ('m', "MULTILINE"), ('s', "DOTALL")] {
+        if flags.contains(flag) {
+            py_flags |= re.getattr(name)?.extract::<i64>()?;
+        }
+    }
+    re.call_method1("compile", (regexp_to_python(&source), py_flags))
+        .map(Bound::unbind)
+        .map_err(|e| TypeConversionError::InvalidValue(format!(
+            "Cannot convert RegExp /{}/{} to re.Pattern: {}", source, flags, e
+        )).into())
+}
+
+// JS 与 Python 命名分组语法不同：(?<name>...) -> (?P<name>...)，\k<name> -> (?P=name)
+fn regexp_to_python(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if chars.peek() == Some(&'k') {
+                    let rest: String = chars.clone().skip(1).collect();
+                    if let Some(name) = rest.strip_prefix('<').and_then(|r| r.split_once('>')).map(|(name, _)| name) {
+                        result.push_str(&format!("(?P={})", name));
+                        for _ in 0..name.chars().count() + 3 {
+                            chars.next();
+                        }
+                        continue;
+                    }
+                }
+                result.push(c);
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            }
+            '(' if chars.clone().take(2).collect::<String>() == "?<" => {
+                let lookbehind = matches!(chars.clone().nth(2), Some('=') | Some('!'));
+                result.push_str(if lookbehind { "(" } else { "(?P" });
+                if !lookbehind {
+                    chars.next();
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// JS 异常或 Promise 拒绝原因转为 Python 异常：Error 对象转换为 JsException 实例直接抛出，
+// 其余值以其字符串形式抛出 JsException
+pub fn js_error_to_py<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    reason: v8::Local<'a, v8::Value>,
+) -> PyErr {
+    match js_to_py(py, scope, reason) {
+        Ok(value) if value.bind(py).is_instance_of::<PyBaseException>() => PyErr::from_value(value.into_bound(py)),
+        Ok(_) => JsException::new_err(exception_message(scope, reason)),
+        Err(e) => e,
+    }
+}
+
+fn handle_error(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+) -> PyResult<PyObject> {
+    let obj = value.to_object(scope)
+        .ok_or_else(|| TypeConversionError::InvalidValue("Failed to convert to Error".to_string()))?;
+    let name = get_property(scope, obj, "name")
+        .map(|name| name.to_rust_string_lossy(scope))
+        .unwrap_or_else(|| "Error".to_string());
+    let message = get_property(scope, obj, "message")
+        .map(|message| message.to_rust_string_lossy(scope))
+        .unwrap_or_default();
+
+    let exception = JsException::new_err(format!("{}: {}", name, message));
+    let instance = exception.value(py);
+    instance.setattr("name", name)?;
+    instance.setattr("message", message)?;
+    let stack = get_property(scope, obj, "stack").map(|stack| stack.to_rust_string_lossy(scope));
+    instance.setattr("stack", stack)?;
+    let cause = match get_property(scope, obj, "cause") {
+        Some(cause) => js_to_py(py, scope, cause)?,
+        None => py.None(),
+    };
+    instance.setattr("cause", cause)?;
+    // 其余自有属性（例如 code）一并保留
+    for (key, value) in object_entries(py, scope, obj)? {
+        if !["name", "message", "stack", "cause"].contains(&key.as_str()) {
+            instance.setattr(key.as_str(), value)?;
+        }
+    }
+    Ok(instance.clone().into_any().unbind())
+}
+
+// 读取属性，属性不存在（undefined）时返回 None
+pub(crate) fn get_property<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: v8::Local<v8::Object>,
+    name: &str,
+) -> Option<v8::Local<'a, v8::Value>> {
+    let key = v8::String::new(scope, name)?;
+    obj.get(scope, key.into()).filter(|value| !value.is_undefined())
+}
+
+// 可枚举自有属性（跳过函数），依次转换为 Python 值
+fn object_entries(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    obj: v8::Local<v8::Object>,
+) -> PyResult<Vec<(String, PyObject)>> {
+    let mut entries = Vec::new();
+    let args = v8::GetPropertyNamesArgs {
+        mode: v8::KeyCollectionMode::OwnOnly,
+        ..Default::default()
+    };
+    if let Some(keys) = obj.get_own_property_names(scope, args) {
+        for i in 0..keys.length() {
+            if let Some(key) = keys.get_index(scope, i) {
+                if let Some(value) = obj.get(scope, key) {
+                    if !value.is_function() {
+                        entries.push((key.to_rust_string_lossy(scope), js_to_py(py, scope, value)?));
+                    }
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+// 沿原型链（不含 Object.prototype）收集 getter 的值，已有的自有属性优先
+fn collect_prototype_getters(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    obj: v8::Local<v8::Object>,
+    py_dict: &Bound<'_, PyDict>,
+) -> PyResult<()> {
+    let get_key = v8::String::new(scope, "get").unwrap();
+    let mut proto = obj.get_prototype(scope).and_then(|p| p.to_object(scope));
+    while let Some(current) = proto {
+        let next = current.get_prototype(scope).filter(|p| !p.is_null());
+        // 原型为 null 的是 Object.prototype（或 Object.create(null) 的对象），不再继续
+        if next.is_none() {
+            break;
+        }
+        let args = v8::GetPropertyNamesArgs {
+            mode: v8::KeyCollectionMode::OwnOnly,
+            property_filter: v8::PropertyFilter::SKIP_SYMBOLS,
+            ..Default::default()
+        };
+        if let Some(keys) = current.get_own_property_names(scope, args) {
+            for i in 0..keys.length() {
+                let Some(key) = keys.get_index(scope, i) else { continue };
+                let Some(name) = key.to_string(scope) else { continue };
+                let name_str = name.to_rust_string_lossy(scope);
+                if py_dict.contains(&name_str)? {
+                    continue;
+                }
+                let has_getter = current.get_own_property_descriptor(scope, name.into())
+                    .and_then(|desc| desc.to_object(scope))
+                    .and_then(|desc| desc.get(scope, get_key.into()))
+                    .is_some_and(|getter| getter.is_function());
+                if has_getter {
+                    if let Some(value) = obj.get(scope, name.into()) {
+                        if !value.is_function() {
+                            py_dict.set_item(name_str, js_to_py(py, scope, value)?)?;
+                        }
+                    }
+                }
+            }
+        }
+        proto = next.and_then(|p| p.to_object(scope));
+    }
+    Ok(())
+}
+
 #[inline]
 fn handle_object(
     py: Python<'_>,
@@ -217,6 +561,9 @@ fn handle_object(
             }
         }
     }
+    if ConvertOptions::get(scope).prototype_getters {
+        collect_prototype_getters(py, scope, obj, &py_dict)?;
+    }
     Ok(py_dict.into())
 }
 
@@ -238,6 +585,10 @@ fn handle_array_buffer(
         (buffer.get_backing_store(), view.byte_offset(), view.byte_length())
     };
 
+    if ConvertOptions::get(scope).zero_copy {
+        return Ok(Py::new(py, JsBuffer::new(store, offset, len, c"B", 1))?.into_any());
+    }
+
     let data_ptr = store.data()
         .ok_or_else(|| TypeConversionError::InvalidValue("Invalid backing store".to_string()))?;
     
@@ -264,6 +615,13 @@ fn handle_typed_array(
         .ok_or_else(|| TypeConversionError::InvalidValue("Detached TypedArray".to_string()))?;
     
     let store = buffer.get_backing_store();
+    let options = ConvertOptions::get(scope);
+    // 零拷贝模式下直接引用 backing store
+    if options.zero_copy {
+        let (format, item_size) = TypedArrayKind::of(value)
+            .map_or((c"B", 1), |kind| (kind.buffer_format(), kind.item_size()));
+        return Ok(Py::new(py, JsBuffer::new(store, view.byte_offset(), view.byte_length(), format, item_size))?.into_any());
+    }
     let data = store.data()
         .ok_or_else(|| TypeConversionError::InvalidValue("Invalid backing store".to_string()))?;
     
@@ -274,7 +632,145 @@ fn handle_typed_array(
         )
     };
 
-    Ok(PyBytes::new(py, bytes).into())
+    // 按元素类型返回 array.array / memoryview / numpy.ndarray
+    let kind = match TypedArrayKind::of(value) {
+        Some(kind) => kind,
+        None => return Ok(PyBytes::new(py, bytes).into()),
+    };
+    match options.typed_array {
+        TypedArrayMode::Bytes => Ok(PyBytes::new(py, bytes).into()),
+        TypedArrayMode::MemoryView => {
+            let memory_view = PyMemoryView::from(PyBytes::new(py, bytes).as_any())?;
+            Ok(memory_view.call_method1("cast", (kind.format(),))?.into())
+        }
+        TypedArrayMode::Numpy => match py.import("numpy") {
+            Ok(numpy) => Ok(numpy.call_method1("frombuffer", (PyByteArray::new(py, bytes), kind.format()))?.into()),
+            Err(_) => new_py_array(py, kind, bytes),
+        },
+        TypedArrayMode::Auto if kind == TypedArrayKind::Uint8 => Ok(PyBytes::new(py, bytes).into()),
+        TypedArrayMode::Auto | TypedArrayMode::Array => new_py_array(py, kind, bytes),
+    }
+}
+
+static ARRAY_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+// array.array 类型，首次使用时导入
+pub(crate) fn array_type(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    ARRAY_TYPE.import(py, "array", "array")
+}
+
+#[inline]
+fn new_py_array(py: Python<'_>, kind: TypedArrayKind, bytes: &[u8]) -> PyResult<PyObject> {
+    Ok(array_type(py)?.call1((kind.format(), PyBytes::new(py, bytes)))?.into())
+}
+
+// TypedArray 的元素类型，与 Python buffer 协议的 format 字符一一对应
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TypedArrayKind {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64,
+}
+
+impl TypedArrayKind {
+    fn of(value: v8::Local<v8::Value>) -> Option<Self> {
+        let kind = match () {
+            _ if value.is_int8_array() => Self::Int8,
+            _ if value.is_uint8_array() => Self::Uint8,
+            _ if value.is_uint8_clamped_array() => Self::Uint8Clamped,
+            _ if value.is_int16_array() => Self::Int16,
+            _ if value.is_uint16_array() => Self::Uint16,
+            _ if value.is_int32_array() => Self::Int32,
+            _ if value.is_uint32_array() => Self::Uint32,
+            _ if value.is_float32_array() => Self::Float32,
+            _ if value.is_float64_array() => Self::Float64,
+            _ if value.is_big_int64_array() => Self::BigInt64,
+            _ if value.is_big_uint64_array() => Self::BigUint64,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn format(self) -> &'static str {
+        self.buffer_format().to_str().unwrap_or("B")
+    }
+
+    // buffer 协议要求以 NUL 结尾的 format
+    fn buffer_format(self) -> &'static CStr {
+        match self {
+            Self::Int8 => c"b",
+            Self::Uint8 | Self::Uint8Clamped => c"B",
+            Self::Int16 => c"h",
+            Self::Uint16 => c"H",
+            Self::Int32 => c"i",
+            Self::Uint32 => c"I",
+            Self::Float32 => c"f",
+            Self::Float64 => c"d",
+            Self::BigInt64 => c"q",
+            Self::BigUint64 => c"Q",
+        }
+    }
+
+    // 解析 struct 模块风格的 format（忽略原生字节序前缀），C long 按平台位宽映射
+    fn from_format(format: &str) -> Option<Self> {
+        let long_is_64 = std::mem::size_of::<std::ffi::c_long>() == 8;
+        let kind = match format.trim_start_matches(['@', '=']) {
+            "b" => Self::Int8,
+            "B" | "c" => Self::Uint8,
+            "h" => Self::Int16,
+            "H" => Self::Uint16,
+            "i" => Self::Int32,
+            "I" => Self::Uint32,
+            "l" if long_is_64 => Self::BigInt64,
+            "l" => Self::Int32,
+            "L" if long_is_64 => Self::BigUint64,
+            "L" => Self::Uint32,
+            "q" => Self::BigInt64,
+            "Q" => Self::BigUint64,
+            "f" => Self::Float32,
+            "d" => Self::Float64,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn item_size(self) -> usize {
+        match self {
+            Self::Int8 | Self::Uint8 | Self::Uint8Clamped => 1,
+            Self::Int16 | Self::Uint16 => 2,
+            Self::Int32 | Self::Uint32 | Self::Float32 => 4,
+            Self::Float64 | Self::BigInt64 | Self::BigUint64 => 8,
+        }
+    }
+
+    fn create<'a>(
+        self,
+        scope: &mut v8::HandleScope<'a>,
+        buffer: v8::Local<v8::ArrayBuffer>,
+        length: usize,
+    ) -> Option<v8::Local<'a, v8::Value>> {
+        match self {
+            Self::Int8 => v8::Int8Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Uint8 => v8::Uint8Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Uint8Clamped => v8::Uint8ClampedArray::new(scope, buffer, 0, length).map(Into::into),
+            Self::Int16 => v8::Int16Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Uint16 => v8::Uint16Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Int32 => v8::Int32Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Uint32 => v8::Uint32Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Float32 => v8::Float32Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::Float64 => v8::Float64Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::BigInt64 => v8::BigInt64Array::new(scope, buffer, 0, length).map(Into::into),
+            Self::BigUint64 => v8::BigUint64Array::new(scope, buffer, 0, length).map(Into::into),
+        }
+    }
 }
 
 #[inline]
@@ -301,22 +797,35 @@ fn handle_map(
     scope: &mut v8::HandleScope,
     map: v8::Local<v8::Map>,
 ) -> PyResult<PyObject> {
-    let py_dict = PyDict::new(py);
     let array = map.as_array(scope);
+    let mut entries = Vec::with_capacity(array.length() as usize / 2);
     
     for i in (0..array.length()).step_by(2) {
         if let (Some(key), Some(value)) = (
             array.get_index(scope, i),
             array.get_index(scope, i + 1)
         ) {
-            py_dict.set_item(
-                js_to_py(py, scope, key)?,
-                js_to_py(py, scope, value)?
-            )?;
+            entries.push((js_to_py(py, scope, key)?, js_to_py(py, scope, value)?));
+        }
+    }
+
+    match ConvertOptions::get(scope).map_mode {
+        MapMode::Dict => {
+            let py_dict = PyDict::new(py);
+            for (key, value) in entries {
+                py_dict.set_item(key, value)?;
+            }
+            Ok(py_dict.into())
+        }
+        MapMode::JsMap => Ok(Py::new(py, JsMap::from_entries(entries))?.into_any()),
+        MapMode::Pairs => {
+            let pairs = entries
+                .into_iter()
+                .map(|(key, value)| PyTuple::new(py, [key, value]))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, pairs)?.into())
         }
     }
-    
-    Ok(py_dict.into())
 }
 
 // #[inline]
@@ -330,42 +839,253 @@ fn handle_map(
 //     Err(PyTypeError::new_err("JavaScript function conversion not implemented"))
 // }
 
-// #[inline]
-// fn handle_promise(
-//     py: Python<'_>,
-//     scope: &mut v8::HandleScope,
-//     value: v8::Local<v8::Value>,
-// ) -> PyResult<PyObject> {
-//     // 创建一个 Python Future 对象
-//     // 这里需要实现具体的异步处理逻辑
-//     Err(PyTypeError::new_err("JavaScript Promise conversion not implemented"))
-// }
+#[inline]
+fn handle_promise<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    value: v8::Local<'a, v8::Value>,
+) -> PyResult<PyObject> {
+    let promise = v8::Local::<v8::Promise>::try_from(value)
+        .map_err(|_| TypeConversionError::InvalidValue("Failed to convert to Promise".to_string()))?;
+    JsPromise::wrap(py, scope, promise)
+}
+
+
+// 安全整数范围内转为 Number，超出范围时按 int_overflow 转为 BigInt 或报错；
+// number_mode="float" 时始终转为 Number，无法用 double 精确表示时报错
+fn handle_py_int<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    if let Ok(n) = obj.extract::<i64>() {
+        if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&n) {
+            return Ok(v8::Number::new(scope, n as f64).into());
+        }
+    }
+
+    let options = ConvertOptions::get(scope);
+    if options.number_mode == NumberMode::Float {
+        // float(int) 超出 double 范围时抛出 OverflowError，否则按舍入结果比较
+        let exact = obj.py().get_type::<PyFloat>().call1((obj,)).ok()
+            .filter(|float| float.eq(obj).unwrap_or(false));
+        return match exact {
+            Some(float) => Ok(v8::Number::new(scope, float.extract()?).into()),
+            None => Err(TypeConversionError::InvalidValue(format!(
+                "Integer {} cannot be represented exactly as a float",
+                obj
+            )).into()),
+        };
+    }
+
+    if options.int_overflow == IntOverflow::Raise {
+        return Err(TypeConversionError::InvalidValue(format!(
+            "Integer {} is outside the JS safe integer range and would lose precision as a Number",
+            obj
+        )).into());
+    }
+
+    let negative = obj.lt(0)?;
+    let magnitude = obj.call_method0("__abs__")?;
+    let bit_length: usize = magnitude.call_method0("bit_length")?.extract()?;
+    let word_count = bit_length.div_ceil(64).max(1);
+    let bytes = magnitude.call_method1("to_bytes", (word_count * 8, "little"))?;
+    let words: Vec<u64> = bytes.downcast::<PyBytes>()?
+        .as_bytes()
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    v8::BigInt::new_from_words(scope, negative, &words)
+        .map(Into::into)
+        .ok_or_else(|| TypeConversionError::InvalidValue("Failed to create BigInt".to_string()).into())
+}
+
+#[inline]
+fn new_array_buffer<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    bytes: &[u8],
+) -> PyResult<v8::Local<'a, v8::ArrayBuffer>> {
+    let len = bytes.len();
+    let buffer = v8::ArrayBuffer::new(scope, len);
+    let store = buffer.get_backing_store();
+    if let Some(data) = store.data() {
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                data.as_ptr() as *mut u8,
+                len
+            );
+        }
+        return Ok(buffer);
+    }
+    if len == 0 {
+        return Ok(buffer);
+    }
+    Err(TypeConversionError::InvalidValue("Failed to get buffer data".to_string()).into())
+}
+
+// 识别带元素类型的 Python 缓冲区对象（bytearray 视为 Uint8）
+fn typed_buffer_kind(obj: &Bound<'_, PyAny>) -> PyResult<Option<TypedArrayKind>> {
+    let py = obj.py();
+    let format = if obj.downcast::<PyByteArray>().is_ok() {
+        "B".to_string()
+    } else if obj.downcast::<PyMemoryView>().is_ok() {
+        obj.getattr("format")?.extract::<String>()?
+    } else if obj.is_instance(array_type(py)?)? {
+        obj.getattr("typecode")?.extract::<String>()?
+    } else if obj.get_type().fully_qualified_name()?.to_str()? == "numpy.ndarray" {
+        obj.getattr("dtype")?.getattr("char")?.extract::<String>()?
+    } else {
+        return Ok(None);
+    };
+
+    TypedArrayKind::from_format(&format)
+        .map(Some)
+        .ok_or_else(|| TypeConversionError::UnsupportedType(format!("Unsupported buffer format: {}", format)).into())
+}
+
+// 零拷贝模式下共享可写的连续缓冲区，否则按 C 顺序拷贝字节
+fn typed_buffer<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
+    kind: TypedArrayKind,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let shared = if ConvertOptions::get(scope).zero_copy {
+        shared_backing_store(obj)
+    } else {
+        None
+    };
+    let (buffer, byte_length) = match shared {
+        Some(store) => {
+            let byte_length = store.byte_length();
+            (v8::ArrayBuffer::with_backing_store(scope, &store), byte_length)
+        }
+        None => {
+            let bytes = obj.py().import("builtins")?.getattr("bytes")?.call1((obj,))?.downcast_into::<PyBytes>()?;
+            (new_array_buffer(scope, bytes.as_bytes())?, bytes.len()?)
+        }
+    };
+    kind.create(scope, buffer, byte_length / kind.item_size())
+        .ok_or_else(|| TypeConversionError::InvalidValue("Failed to create TypedArray".to_string()).into())
+}
+
+// to_plain_value 对每个无法直接转换的对象都会调用，缓存所需的类型与函数
+static DECIMAL_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static ENUM_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static UUID_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static IS_DATACLASS: GILOnceCell<PyObject> = GILOnceCell::new();
+static DATACLASS_ASDICT: GILOnceCell<PyObject> = GILOnceCell::new();
+
+// 自定义对象转换为基础 Python 值，依次尝试：
+// __js__ 协议、Enum 的值、NamedTuple/dataclass/pydantic 模型转字典、UUID 转字符串、
+// Decimal 可被 double 精确表示（或为 NaN/Infinity）时转 float，否则转字符串以免丢失精度
+// 不需要转换时返回 None
+pub(crate) fn to_plain_value<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = obj.py();
+    // 内置类型直接跳过；类对象本身带有这些属性，只转换实例
+    if obj.is_exact_instance_of::<PyString>()
+        || obj.is_exact_instance_of::<PyInt>()
+        || obj.is_exact_instance_of::<PyFloat>()
+        || obj.is_exact_instance_of::<PyBool>()
+        || obj.is_exact_instance_of::<PyList>()
+        || obj.is_exact_instance_of::<PyDict>()
+        || obj.is_instance_of::<PyType>()
+    {
+        return Ok(None);
+    }
 
+    if obj.hasattr("__js__")? {
+        let converted = obj.call_method0("__js__")?;
+        if converted.is(obj) {
+            return Err(TypeConversionError::InvalidValue(format!(
+                "{}.__js__() returned itself",
+                obj.get_type().name()?
+            )).into());
+        }
+        return Ok(Some(converted));
+    }
+
+    if obj.is_instance(ENUM_TYPE.import(py, "enum", "Enum")?)? {
+        return Ok(Some(obj.getattr("value")?));
+    }
+
+    if obj.is_instance_of::<PyTuple>() && obj.hasattr("_asdict")? {
+        return Ok(Some(obj.call_method0("_asdict")?));
+    }
+
+    if IS_DATACLASS.import(py, "dataclasses", "is_dataclass")?.call1((obj,))?.is_truthy()? {
+        return Ok(Some(DATACLASS_ASDICT.import(py, "dataclasses", "asdict")?.call1((obj,))?));
+    }
+
+    if obj.hasattr("model_dump")? {
+        return Ok(Some(obj.call_method0("model_dump")?));
+    }
+
+    if obj.is_instance(UUID_TYPE.import(py, "uuid", "UUID")?)? {
+        return Ok(Some(obj.str()?.into_any()));
+    }
+
+    if obj.is_instance(DECIMAL_TYPE.import(py, "decimal", "Decimal")?)? {
+        let float = py.get_type::<PyFloat>().call1((obj,))?;
+        let exact = !obj.call_method0("is_finite")?.is_truthy()?
+            || obj.get_type().call1((&float,))?.eq(obj)?;
+        return Ok(Some(if exact { float } else { obj.str()?.into_any() }));
+    }
+
+    Ok(None)
+}
 
 pub fn py_to_js<'a>(
     scope: &mut v8::HandleScope<'a>,
     obj: &Bound<'_, PyAny>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    // 优先使用按 Python 类型注册的转换器
+    let converter = match ConverterRegistry::get(scope) {
+        Some(registry) => registry.find_py(obj)?,
+        None => None,
+    };
+    let Some(converter) = converter else {
+        return py_to_js_builtin(scope, obj);
+    };
+
+    let value = match &converter.to_js {
+        Some(to_js) => {
+            let converted = to_js.call1(obj.py(), (obj,))?.into_bound(obj.py());
+            // 返回对象本身时不再查找转换器，避免无限递归
+            if converted.is(obj) {
+                py_to_js_builtin(scope, &converted)?
+            } else {
+                py_to_js(scope, &converted)?
+            }
+        }
+        None => py_to_js_builtin(scope, obj)?,
+    };
+    match &converter.js {
+        Some(func) => call_converter(scope, func, value),
+        None => Ok(value),
+    }
+}
+
+fn py_to_js_builtin<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
 ) -> PyResult<v8::Local<'a, v8::Value>> {
     if obj.is_none() {
         return Ok(v8::null(scope).into());
     }
+
+    // 自定义对象先转换为可表示的 Python 值（__js__ 协议、Enum、dataclass 等）
+    if let Some(converted) = to_plain_value(obj)? {
+        return py_to_js(scope, &converted);
+    }
     
     // 字符串处理
     if let Ok(s) = obj.extract::<String>() {
         return Ok(v8::String::new(scope, &s).unwrap().into());
     }
     
-    // 整数类型处理
-    if let Ok(n) = obj.extract::<i64>() {
-        return Ok(v8::Number::new(scope, n as f64).into());
-    }
-
-    if let Ok(n) = obj.extract::<i32>() {
-        return Ok(v8::Number::new(scope, n as f64).into());
-    }
-
-    if let Ok(n) = obj.extract::<u32>() {
-        return Ok(v8::Number::new(scope, n as f64).into());
+    // 整数类型处理（bool 是 int 的子类，交给后面的布尔分支）
+    if obj.is_instance_of::<PyInt>() && !obj.is_instance_of::<PyBool>() {
+        return handle_py_int(scope, obj);
     }
 
     // 浮点数处理
@@ -388,20 +1108,7 @@ pub fn py_to_js<'a>(
     
     // 字节数据处理
     if let Ok(bytes) = obj.downcast::<PyBytes>() {
-        let len = bytes.len()? as usize;
-        let buffer = v8::ArrayBuffer::new(scope, len);
-        let store = buffer.get_backing_store();
-        if let Some(data) = store.data() {
-            unsafe {
-                std::ptr::copy_nonoverlapping(
-                    bytes.as_bytes().as_ptr(),
-                    data.as_ptr() as *mut u8,
-                    len
-                );
-            }
-            return Ok(buffer.into());
-        }
-        return Err(TypeConversionError::InvalidValue("Failed to get buffer data".to_string()).into());
+        return Ok(new_array_buffer(scope, bytes.as_bytes())?.into());
     }
 
     // 日期时间处理
@@ -409,6 +1116,18 @@ pub fn py_to_js<'a>(
         let timestamp = dt.call_method0("timestamp")?.extract::<f64>()?;
         return Ok(v8::Date::new(scope, timestamp * 1000.0).unwrap().into());
     }
+
+    // 日期转为当天 UTC 零点的 Date
+    if let Ok(date) = obj.downcast::<PyDate>() {
+        let days = date.call_method0("toordinal")?.extract::<i64>()? - UNIX_EPOCH_ORDINAL;
+        return Ok(v8::Date::new(scope, (days * MS_PER_DAY) as f64).unwrap().into());
+    }
+
+    // JS 没有单独的时间类型，time 转为 ISO 格式字符串
+    if let Ok(time) = obj.downcast::<PyTime>() {
+        let iso = time.call_method0("isoformat")?.extract::<String>()?;
+        return Ok(v8::String::new(scope, &iso).unwrap().into());
+    }
     
     // 列表处理
     if let Ok(list) = obj.downcast::<PyList>() {
@@ -419,6 +1138,56 @@ pub fn py_to_js<'a>(
         }
         return Ok(array.into());
     }
+
+    // 元组处理（NamedTuple 已在前面转为字典）
+    if let Ok(tuple) = obj.downcast::<PyTuple>() {
+        let array = v8::Array::new(scope, tuple.len() as i32);
+        for (i, item) in tuple.iter().enumerate() {
+            let js_value = py_to_js(scope, &item)?;
+            array.set_index(scope, i as u32, js_value).unwrap();
+        }
+        return Ok(array.into());
+    }
+
+    // JsSymbol 转为具有相同描述的新 Symbol
+    if let Ok(symbol) = obj.downcast::<JsSymbol>() {
+        let description = symbol.get().description()
+            .and_then(|description| v8::String::new(scope, description));
+        return Ok(v8::Symbol::new(scope, description).into());
+    }
+
+    // 同一 runtime 中得到的 JsPromise 与 JS 迭代器还原为原来的 JS 对象
+    if let Ok(promise) = obj.downcast::<JsPromise>() {
+        if let Some(promise) = promise.borrow().local(scope) {
+            return Ok(promise);
+        }
+    }
+    if let Some(iterator) = iterator_local(scope, obj) {
+        return Ok(iterator);
+    }
+
+    // JsMap 转为 Map，键可以是任意值
+    if let Ok(map) = obj.downcast::<JsMap>() {
+        let js_map = v8::Map::new(scope);
+        for (key, value) in map.borrow().entries() {
+            let js_key = py_to_js(scope, key.bind(obj.py()))?;
+            let js_value = py_to_js(scope, value.bind(obj.py()))?;
+            js_map.set(scope, js_key, js_value)
+                .ok_or_else(|| TypeConversionError::InvalidValue("Failed to set Map entry".to_string()))?;
+        }
+        return Ok(js_map.into());
+    }
+
+    // set / frozenset 转为 Set
+    if obj.is_instance_of::<PySet>() || obj.is_instance_of::<PyFrozenSet>() {
+        let set = v8::Set::new(scope);
+        for item in obj.try_iter()? {
+            let js_value = py_to_js(scope, &item?)?;
+            set.add(scope, js_value)
+                .ok_or_else(|| TypeConversionError::InvalidValue("Failed to add value to Set".to_string()))?;
+        }
+        return Ok(set.into());
+    }
     
     // 字典处理
     if let Ok(dict) = obj.downcast::<PyDict>() {
@@ -433,6 +1202,21 @@ pub fn py_to_js<'a>(
         return Ok(js_obj.into());
     }
     
+    // bytearray / array.array / memoryview / numpy.ndarray 转为对应元素类型的 TypedArray
+    if let Some(kind) = typed_buffer_kind(obj)? {
+        return typed_buffer(scope, obj, kind);
+    }
+
+    // 协程、asyncio.Future 与 concurrent.futures.Future 转为 Promise
+    if py_async::is_awaitable(obj)? {
+        return py_async::awaitable_to_promise(scope, obj);
+    }
+
+    // 生成器等可迭代对象（含异步可迭代对象）转为 JS 迭代器
+    if let Some(iterator) = py_iterable_to_js(scope, obj)? {
+        return Ok(iterator);
+    }
+
     Err(TypeConversionError::InvalidValue(format!(
         "Unsupported Python type: {}",
         obj.get_type().to_string()
@@ -1,5 +1,12 @@
 use std::fmt;
 use pyo3::prelude::*;
+use pyo3::exceptions::{PyException, PyRuntimeError};
+
+// JS Error 对象转换后的 Python 异常，保留 name、message 与 stack 属性
+pyo3::create_exception!(py_js_runtime, JsException, PyException);
+
+// isolation="process" 的子进程崩溃（V8 致命错误、OOM 等）或超过 worker_timeout 未响应时抛出，子进程在下次调用时重启
+pyo3::create_exception!(py_js_runtime, JsWorkerError, PyRuntimeError);
 
 #[derive(Debug)]
 pub enum TypeConversionError {
@@ -14,6 +21,7 @@ pub enum JsError {
     RuntimeError(String),
     ExecutionError(String),
     JsonError(String),
+    ConversionError(String),
 }
 
 impl fmt::Display for TypeConversionError {
@@ -33,6 +41,7 @@ impl fmt::Display for JsError {
             Self::RuntimeError(msg) => write!(f, "Runtime error: {}", msg),
             Self::ExecutionError(msg) => write!(f, "Execution error: {}", msg),
             Self::JsonError(msg) => write!(f, "JSON error: {}", msg),
+            Self::ConversionError(msg) => write!(f, "Conversion error: {}", msg),
         }
     }
 }
@@ -0,0 +1,317 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyStopIteration, PyTypeError};
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyModule;
+use std::ffi::CStr;
+use std::sync::{Arc, Weak};
+use crate::engine::engine_thread::{EngineBound, ThreadBound};
+use crate::engine::py_async;
+use crate::engine::runtime_cell::RuntimeCell;
+use crate::types::convert::{get_property, js_error_to_py, js_to_py, py_to_js};
+use crate::types::error::JsError;
+use crate::types::promise::{JsPromise, RuntimeRef};
+use crate::types::proxy;
+
+// JS 迭代器与 Python 迭代器之间的惰性桥接：每次 next 跨边界调用一次
+
+// 异步迭代一步，结果为 IteratorResult 形式的 dict，经代理在 JS 中读取 done/value
+const ANEXT_STEP: &CStr = c"
+async def anext_step(iterator):
+    try:
+        return {'done': False, 'value': await iterator.__anext__()}
+    except StopAsyncIteration:
+        return {'done': True, 'value': None}
+";
+
+static ANEXT_STEP_FN: GILOnceCell<PyObject> = GILOnceCell::new();
+
+// JS 同步迭代器，按 Python 迭代器协议逐个取值；访问转交 runtime 所在线程执行
+#[pyclass(name = "JsIterator")]
+pub struct JsIterator {
+    inner: ThreadBound<IteratorRef>,
+}
+
+// JS 异步迭代器，__anext__ 返回可 await 的 JsPromise
+#[pyclass(name = "JsAsyncIterator")]
+pub struct JsAsyncIterator {
+    inner: ThreadBound<IteratorRef>,
+}
+
+struct IteratorRef {
+    iterator: v8::Global<v8::Object>,
+    runtime: Weak<RuntimeCell>,
+}
+
+// SAFETY: 同 PromiseRef
+unsafe impl EngineBound for IteratorRef {}
+
+// 实现了 Symbol.asyncIterator 或 Symbol.iterator 的 JS 对象转为对应的 Python 迭代器
+pub fn handle_iterable<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    object: v8::Local<'a, v8::Object>,
+) -> PyResult<Option<PyObject>> {
+    let async_symbol = v8::Symbol::get_async_iterator(scope);
+    if let Some(iterator) = get_iterator(py, scope, object, async_symbol)? {
+        let inner = ThreadBound::new(IteratorRef { iterator, runtime: RuntimeRef::get(scope)? });
+        return Ok(Some(Py::new(py, JsAsyncIterator { inner })?.into_any()));
+    }
+    let symbol = v8::Symbol::get_iterator(scope);
+    if let Some(iterator) = get_iterator(py, scope, object, symbol)? {
+        let inner = ThreadBound::new(IteratorRef { iterator, runtime: RuntimeRef::get(scope)? });
+        return Ok(Some(Py::new(py, JsIterator { inner })?.into_any()));
+    }
+    Ok(None)
+}
+
+// 调用 object[symbol]() 取得迭代器，方法不存在时返回 None
+fn get_iterator<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    object: v8::Local<'a, v8::Object>,
+    symbol: v8::Local<'a, v8::Symbol>,
+) -> PyResult<Option<v8::Global<v8::Object>>> {
+    let Some(method) = object.get(scope, symbol.into()) else {
+        return Ok(None);
+    };
+    let Ok(method) = v8::Local::<v8::Function>::try_from(method) else {
+        return Ok(None);
+    };
+    let iterator = call(py, scope, method, object)?;
+    let iterator = v8::Local::<v8::Object>::try_from(iterator)
+        .map_err(|_| PyTypeError::new_err("Result of the iterator method is not an object"))?;
+    Ok(Some(v8::Global::new(scope, iterator)))
+}
+
+fn call<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    func: v8::Local<'a, v8::Function>,
+    recv: v8::Local<'a, v8::Object>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let mut try_catch = v8::TryCatch::new(scope);
+    match func.call(&mut try_catch, recv.into(), &[]) {
+        Some(result) => Ok(result),
+        None => match try_catch.exception() {
+            Some(exception) => Err(js_error_to_py(py, &mut try_catch, exception)),
+            None => Err(JsError::ExecutionError("Iterator call failed".to_string()).into()),
+        },
+    }
+}
+
+// 调用迭代器的 next()
+fn call_next<'a>(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope<'a>,
+    iterator: &v8::Global<v8::Object>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let iterator = v8::Local::new(scope, iterator);
+    let next = get_property(scope, iterator, "next")
+        .and_then(|next| v8::Local::<v8::Function>::try_from(next).ok())
+        .ok_or_else(|| PyTypeError::new_err("Iterator has no next method"))?;
+    call(py, scope, next, iterator)
+}
+
+// 属于当前 runtime 时还原为原来的迭代器对象
+pub fn iterator_local<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
+) -> Option<v8::Local<'a, v8::Value>> {
+    let iterator = if let Ok(iterator) = obj.downcast::<JsIterator>() {
+        iterator.borrow().inner.get().map(|inner| (inner.iterator.clone(), inner.runtime.clone()))
+    } else if let Ok(iterator) = obj.downcast::<JsAsyncIterator>() {
+        iterator.borrow().inner.get().map(|inner| (inner.iterator.clone(), inner.runtime.clone()))
+    } else {
+        None
+    };
+    let (iterator, runtime) = iterator?;
+    RuntimeRef::owns(scope, &runtime).then(|| v8::Local::new(scope, iterator).into())
+}
+
+impl IteratorRef {
+    fn runtime(&self) -> PyResult<Arc<RuntimeCell>> {
+        self.runtime.upgrade().ok_or_else(|| JsError::RuntimeError("JsRuntime has been dropped".to_string()).into())
+    }
+
+    fn next_value(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let runtime = self.runtime()?;
+        let mut runtime = runtime.enter()?;
+        runtime.with_scope(|scope| {
+            let result = call_next(py, scope, &self.iterator)?;
+            let result = v8::Local::<v8::Object>::try_from(result)
+                .map_err(|_| PyTypeError::new_err("Iterator result is not an object"))?;
+            if get_property(scope, result, "done").is_some_and(|done| done.boolean_value(scope)) {
+                return Ok(None);
+            }
+            match get_property(scope, result, "value") {
+                Some(value) => js_to_py(py, scope, value).map(Some),
+                None => Ok(Some(py.None())),
+            }
+        })
+    }
+
+    // next() 返回的 Promise 兑现为 IteratorResult，done 时 await 抛出 StopAsyncIteration
+    fn next_promise(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let runtime = self.runtime()?;
+        let mut runtime = runtime.enter()?;
+        runtime.with_scope(|scope| {
+            let result = call_next(py, scope, &self.iterator)?;
+            let promise = match v8::Local::<v8::Promise>::try_from(result) {
+                Ok(promise) => promise,
+                Err(_) => {
+                    let resolver = v8::PromiseResolver::new(scope)
+                        .ok_or_else(|| JsError::RuntimeError("Failed to create promise".to_string()))?;
+                    resolver.resolve(scope, result);
+                    resolver.get_promise(scope)
+                }
+            };
+            JsPromise::wrap_step(py, scope, promise)
+        })
+    }
+}
+
+#[pymethods]
+impl JsIterator {
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __next__(&self) -> PyResult<Option<PyObject>> {
+        self.inner.run(|inner| Python::with_gil(|py| inner.next_value(py)))?
+    }
+}
+
+#[pymethods]
+impl JsAsyncIterator {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__(&self) -> PyResult<PyObject> {
+        self.inner.run(|inner| Python::with_gil(|py| inner.next_promise(py)))?
+    }
+}
+
+// Python 异步可迭代对象与可迭代对象转为实现 Symbol.asyncIterator / Symbol.iterator 的 JS 迭代器
+pub fn py_iterable_to_js<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
+) -> PyResult<Option<v8::Local<'a, v8::Value>>> {
+    let (iterator, is_async) = if obj.hasattr("__aiter__")? {
+        (obj.call_method0("__aiter__")?, true)
+    } else if obj.hasattr("__iter__")? {
+        (obj.try_iter()?.into_any(), false)
+    } else {
+        return Ok(None);
+    };
+    // 迭代器由代理对象持有，随 JS 迭代器一起回收
+    let holder: v8::Local<v8::Value> = proxy::wrap(scope, &iterator)?.into();
+    let object = v8::Object::new(scope);
+    let (symbol, next) = if is_async {
+        (v8::Symbol::get_async_iterator(scope), v8::Function::builder(async_iterator_next))
+    } else {
+        (v8::Symbol::get_iterator(scope), v8::Function::builder(iterator_next))
+    };
+    let next = next.data(holder).build(scope)
+        .ok_or_else(|| JsError::RuntimeError("Failed to create function".to_string()))?;
+    set(scope, object, "next", next.into());
+    if !is_async {
+        let close = v8::Function::builder(iterator_return).data(holder).build(scope)
+            .ok_or_else(|| JsError::RuntimeError("Failed to create function".to_string()))?;
+        set(scope, object, "return", close.into());
+    }
+    let this = v8::Function::new(scope, |_: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, mut rv: v8::ReturnValue| {
+        rv.set(args.this().into());
+    }).ok_or_else(|| JsError::RuntimeError("Failed to create function".to_string()))?;
+    object.set(scope, symbol.into(), this.into());
+    Ok(Some(object.into()))
+}
+
+fn set(scope: &mut v8::HandleScope, object: v8::Local<v8::Object>, key: &str, value: v8::Local<v8::Value>) {
+    if let Some(key) = v8::String::new(scope, key) {
+        object.set(scope, key.into(), value);
+    }
+}
+
+fn iter_result<'a>(scope: &mut v8::HandleScope<'a>, done: bool, value: v8::Local<'a, v8::Value>) -> v8::Local<'a, v8::Object> {
+    let result = v8::Object::new(scope);
+    let done = v8::Boolean::new(scope, done).into();
+    set(scope, result, "done", done);
+    set(scope, result, "value", value);
+    result
+}
+
+fn iterator_next<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    Python::with_gil(|py| {
+        let Some(iterator) = proxy::unwrap(py, scope, args.data()) else {
+            return;
+        };
+        let result = match iterator.bind(py).call_method0("__next__") {
+            Ok(value) => py_to_js(scope, &value).map(|value| (false, value)),
+            // 生成器的返回值作为 done 时的 value
+            Err(e) if e.is_instance_of::<PyStopIteration>(py) => e.value(py).getattr("value")
+                .and_then(|value| py_to_js(scope, &value))
+                .map(|value| (true, value)),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok((done, value)) => rv.set(iter_result(scope, done, value).into()),
+            Err(e) => proxy::throw(scope, e),
+        }
+    })
+}
+
+// for...of 提前退出时关闭生成器
+fn iterator_return<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    Python::with_gil(|py| {
+        let Some(iterator) = proxy::unwrap(py, scope, args.data()) else {
+            return;
+        };
+        let iterator = iterator.bind(py);
+        let closed = match iterator.hasattr("close") {
+            Ok(true) => iterator.call_method0("close").map(|_| ()),
+            Ok(false) => Ok(()),
+            Err(e) => Err(e),
+        };
+        match closed {
+            Ok(()) => {
+                let value = args.get(0);
+                rv.set(iter_result(scope, true, value).into());
+            }
+            Err(e) => proxy::throw(scope, e),
+        }
+    })
+}
+
+fn async_iterator_next<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    Python::with_gil(|py| {
+        let Some(iterator) = proxy::unwrap(py, scope, args.data()) else {
+            return;
+        };
+        let result = ANEXT_STEP_FN
+            .get_or_try_init(py, || {
+                PyModule::from_code(py, ANEXT_STEP, c"py_js_runtime_iterator.py", c"py_js_runtime_iterator")?
+                    .getattr("anext_step")
+                    .map(Bound::unbind)
+            })
+            .and_then(|anext_step| anext_step.call1(py, (iterator,)))
+            .and_then(|step| py_async::awaitable_to_promise(scope, step.bind(py)));
+        match result {
+            Ok(promise) => rv.set(promise),
+            Err(e) => proxy::throw(scope, e),
+        }
+    })
+}
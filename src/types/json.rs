@@ -0,0 +1,63 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use crate::engine::v8engine::exception_message;
+use crate::types::error::{JsError, TypeConversionError};
+
+// 大量纯 JSON 数据的快速通道：一次 json.dumps + JSON.parse（或 JSON.stringify + json.loads）
+// 代替逐个键值的递归转换，只支持 JSON 能表示的类型。
+// 语义固定为标准 JSON：不使用 ConvertOptions（int_overflow、number_mode、map_mode 等）与注册的转换器，
+// 数字按 json 模块与 JSON.parse 的规则转换，Map/Set 等按 JSON.stringify 的规则序列化
+
+// Python 值经 json.dumps 序列化后在 JS 中解析
+pub fn py_to_js_json<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    obj: &Bound<'_, PyAny>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let json = obj.py().import("json")?
+        .call_method1("dumps", (obj,))
+        .map_err(|e| TypeConversionError::SerializationError(e.to_string()))?
+        .extract::<String>()?;
+    parse_json(scope, &json)
+}
+
+// JS 值经 JSON.stringify 序列化后由 json.loads 解析
+pub fn js_to_py_json(
+    py: Python<'_>,
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+) -> PyResult<PyObject> {
+    let json = js_to_json(scope, value)?;
+    py.import("json")?
+        .call_method1("loads", (json,))
+        .map(Bound::unbind)
+        .map_err(|e| TypeConversionError::DeserializationError(e.to_string()).into())
+}
+
+pub fn parse_json<'a>(scope: &mut v8::HandleScope<'a>, json: &str) -> PyResult<v8::Local<'a, v8::Value>> {
+    let mut try_catch = v8::TryCatch::new(scope);
+    let source = v8::String::new(&mut try_catch, json)
+        .ok_or_else(|| JsError::JsonError("JSON string is too large".to_string()))?;
+    match v8::json::parse(&mut try_catch, source) {
+        Some(value) => Ok(value),
+        None => Err(JsError::JsonError(pending_message(&mut try_catch)).into()),
+    }
+}
+
+// undefined 等 JSON.stringify 不产生结果的值序列化为 null
+pub fn js_to_json(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> PyResult<String> {
+    let mut try_catch = v8::TryCatch::new(scope);
+    match v8::json::stringify(&mut try_catch, value) {
+        Some(json) if !json.strict_equals(v8::undefined(&mut try_catch).into()) => {
+            Ok(json.to_rust_string_lossy(&mut try_catch))
+        }
+        Some(_) => Ok("null".to_string()),
+        None if try_catch.has_caught() => Err(JsError::JsonError(pending_message(&mut try_catch)).into()),
+        None => Ok("null".to_string()),
+    }
+}
+
+fn pending_message(try_catch: &mut v8::TryCatch<v8::HandleScope>) -> String {
+    try_catch.exception()
+        .map(|exception| exception_message(try_catch, exception))
+        .unwrap_or_else(|| "Unknown JSON error".to_string())
+}
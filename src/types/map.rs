@@ -0,0 +1,125 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyKeyError;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+// 保留插入顺序和任意键的 JS Map，也可作为标记把 Python dict 以 Map 传给 JS
+// 键按 Python 的 == 比较，列表等不可哈希的值也能作为键
+#[pyclass(name = "JsMap")]
+pub struct JsMap {
+    entries: Vec<(PyObject, PyObject)>,
+}
+
+impl JsMap {
+    pub fn from_entries(entries: Vec<(PyObject, PyObject)>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[(PyObject, PyObject)] {
+        &self.entries
+    }
+
+    fn position(&self, key: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+        for (i, (k, _)) in self.entries.iter().enumerate() {
+            if k.bind(key.py()).eq(key)? {
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[pymethods]
+impl JsMap {
+    // 接受 dict、JsMap 或 (key, value) 序列
+    #[new]
+    #[pyo3(signature = (entries=None))]
+    fn new(entries: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut map = Self::from_entries(Vec::new());
+        let Some(entries) = entries else {
+            return Ok(map);
+        };
+        let items = if entries.is_instance_of::<PyDict>() || entries.is_instance_of::<Self>() {
+            entries.call_method0("items")?
+        } else {
+            entries.clone()
+        };
+        for item in items.try_iter()? {
+            let (key, value) = item?.extract::<(PyObject, PyObject)>()?;
+            map.__setitem__(key.bind(entries.py()), value)?;
+        }
+        Ok(map)
+    }
+
+    fn __len__(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn __contains__(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.position(key)?.is_some())
+    }
+
+    fn __getitem__(&self, key: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        match self.position(key)? {
+            Some(i) => Ok(self.entries[i].1.clone_ref(key.py())),
+            None => Err(PyKeyError::new_err(key.clone().unbind())),
+        }
+    }
+
+    fn __setitem__(&mut self, key: &Bound<'_, PyAny>, value: PyObject) -> PyResult<()> {
+        match self.position(key)? {
+            Some(i) => self.entries[i].1 = value,
+            None => self.entries.push((key.clone().unbind(), value)),
+        }
+        Ok(())
+    }
+
+    fn __delitem__(&mut self, key: &Bound<'_, PyAny>) -> PyResult<()> {
+        match self.position(key)? {
+            Some(i) => {
+                self.entries.remove(i);
+                Ok(())
+            }
+            None => Err(PyKeyError::new_err(key.clone().unbind())),
+        }
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyList::new(py, self.keys(py))?.into_any().try_iter()?.into_any())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("JsMap({})", PyList::new(py, self.items(py)?)?.repr()?))
+    }
+
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python<'_>, key: &Bound<'_, PyAny>, default: Option<PyObject>) -> PyResult<PyObject> {
+        match self.position(key)? {
+            Some(i) => Ok(self.entries[i].1.clone_ref(py)),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    fn keys(&self, py: Python<'_>) -> Vec<PyObject> {
+        self.entries.iter().map(|(k, _)| k.clone_ref(py)).collect()
+    }
+
+    fn values(&self, py: Python<'_>) -> Vec<PyObject> {
+        self.entries.iter().map(|(_, v)| v.clone_ref(py)).collect()
+    }
+
+    fn items<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyTuple>>> {
+        self.entries
+            .iter()
+            .map(|(k, v)| PyTuple::new(py, [k.clone_ref(py), v.clone_ref(py)]))
+            .collect()
+    }
+
+    // 转为 dict，键不可哈希时抛出 TypeError
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (k, v) in &self.entries {
+            dict.set_item(k, v)?;
+        }
+        Ok(dict)
+    }
+}
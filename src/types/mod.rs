@@ -1,2 +1,12 @@
+pub mod buffer;
 pub mod convert;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod iterator;
+pub mod json;
+pub mod map;
+pub mod options;
+pub mod promise;
+pub mod proxy;
+pub mod registry;
+pub mod symbol;
+pub mod wire;
\ No newline at end of file
@@ -0,0 +1,157 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+// 超出 JS 安全整数范围（±(2^53 - 1)）的 Python int 的处理方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntOverflow {
+    // 转为 BigInt
+    #[default]
+    BigInt,
+    // 抛出异常
+    Raise,
+}
+
+impl IntOverflow {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "bigint" => Ok(Self::BigInt),
+            "raise" => Ok(Self::Raise),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid int_overflow {:?}, expected \"bigint\" or \"raise\"",
+                mode
+            ))),
+        }
+    }
+}
+
+// JS Number 转为 Python 时的取整策略
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberMode {
+    // 安全整数范围内的整数值返回 int，其余返回 float
+    #[default]
+    Auto,
+    // 仅 Smi/Int32 表示的值返回 int
+    Int32,
+    // 始终返回 float；Python int 也始终转为 Number，无法精确表示时报错
+    Float,
+}
+
+impl NumberMode {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "auto" => Ok(Self::Auto),
+            "int32" => Ok(Self::Int32),
+            "float" => Ok(Self::Float),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid number_mode {:?}, expected \"auto\", \"int32\" or \"float\"",
+                mode
+            ))),
+        }
+    }
+}
+
+// JS TypedArray 转为 Python 时使用的类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TypedArrayMode {
+    // Uint8Array 与未知类型返回 bytes（与此前行为一致），其余返回 array.array
+    #[default]
+    Auto,
+    // array.array，保留元素类型（包括 Uint8Array）
+    Array,
+    // 带 format 的 memoryview
+    MemoryView,
+    // numpy.ndarray，未安装 numpy 时退回 array.array
+    Numpy,
+    // 原始 bytes
+    Bytes,
+}
+
+impl TypedArrayMode {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "auto" => Ok(Self::Auto),
+            "array" => Ok(Self::Array),
+            "memoryview" => Ok(Self::MemoryView),
+            "numpy" => Ok(Self::Numpy),
+            "bytes" => Ok(Self::Bytes),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid typed_array {:?}, expected \"auto\", \"array\", \"memoryview\", \"numpy\" or \"bytes\"",
+                mode
+            ))),
+        }
+    }
+}
+
+// JS Map 转为 Python 时使用的类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapMode {
+    // dict，键必须可哈希
+    #[default]
+    Dict,
+    // JsMap，保留任意键且可原样传回 JS
+    JsMap,
+    // (key, value) 元组列表
+    Pairs,
+}
+
+impl MapMode {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "dict" => Ok(Self::Dict),
+            "jsmap" => Ok(Self::JsMap),
+            "pairs" => Ok(Self::Pairs),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid map_mode {:?}, expected \"dict\", \"jsmap\" or \"pairs\"",
+                mode
+            ))),
+        }
+    }
+}
+
+// 函数调用时参数与返回值的传递方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transfer {
+    // 逐个值递归转换，支持全部类型
+    #[default]
+    Native,
+    // 整体经 JSON 序列化传递，适合大量纯 JSON 数据；
+    // 按标准 JSON 语义转换，不应用转换选项与注册的转换器
+    Json,
+}
+
+impl Transfer {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "native" => Ok(Self::Native),
+            "json" => Ok(Self::Json),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid transfer {:?}, expected \"native\" or \"json\"",
+                mode
+            ))),
+        }
+    }
+}
+
+// 类型转换选项，保存在 isolate 的 slot 中供 js_to_py/py_to_js 读取
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConvertOptions {
+    pub int_overflow: IntOverflow,
+    pub number_mode: NumberMode,
+    pub typed_array: TypedArrayMode,
+    // TypedArray/ArrayBuffer 与可写的 Python 缓冲区共享内存而不拷贝
+    pub zero_copy: bool,
+    pub map_mode: MapMode,
+    // 普通对象转换时是否包含原型链上 getter 的值（例如 class 中定义的 get 属性）
+    pub prototype_getters: bool,
+}
+
+impl ConvertOptions {
+    pub fn get(isolate: &v8::Isolate) -> Self {
+        isolate.get_slot::<Self>().copied().unwrap_or_default()
+    }
+
+    pub fn install(self, isolate: &mut v8::Isolate) {
+        isolate.set_slot(self);
+    }
+}
@@ -0,0 +1,252 @@
+use deno_core::{v8, PollEventLoopOptions};
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration, PyStopIteration, PyTypeError};
+use pyo3::types::PyCFunction;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Wake, Waker};
+use crate::engine::engine_thread::{EngineBound, ThreadBound};
+use crate::engine::runtime_cell::{RuntimeCell, RuntimeGuard};
+use crate::engine::v8engine::block_on_without_gil;
+use crate::types::convert::{get_property, js_error_to_py, js_to_py};
+use crate::types::error::JsError;
+
+// JsPromise 经 isolate slot 中的弱引用访问所属 runtime，避免循环引用
+pub struct RuntimeRef(pub Weak<RuntimeCell>);
+
+impl RuntimeRef {
+    pub fn get(isolate: &v8::Isolate) -> PyResult<Weak<RuntimeCell>> {
+        isolate.get_slot::<Self>()
+            .map(|runtime| runtime.0.clone())
+            .ok_or_else(|| JsError::RuntimeError("No JsRuntime is associated with this isolate".to_string()).into())
+    }
+
+    // 判断 runtime 是否为该 isolate 所属的 runtime
+    pub fn owns(isolate: &v8::Isolate, runtime: &Weak<RuntimeCell>) -> bool {
+        isolate.get_slot::<Self>().is_some_and(|current| current.0.ptr_eq(runtime))
+    }
+}
+
+// 结果中嵌套的 JS Promise：可在 asyncio 中 await，也可调用 result() 同步等待
+// 等待期间驱动 JS 事件循环；可在任意线程中使用，访问转交 runtime 所在线程执行
+#[pyclass(name = "JsPromise")]
+pub struct JsPromise {
+    inner: ThreadBound<PromiseRef>,
+    // 异步迭代器的一步：兑现值为 IteratorResult，done 时抛出 StopAsyncIteration
+    step: bool,
+}
+
+struct PromiseRef {
+    promise: v8::Global<v8::Promise>,
+    runtime: Weak<RuntimeCell>,
+}
+
+// SAFETY: Global 与 Weak 只在引擎线程上解引用和释放，移动本身不涉及线程相关状态
+unsafe impl EngineBound for PromiseRef {}
+
+impl JsPromise {
+    pub fn wrap<'a>(
+        py: Python<'_>,
+        scope: &mut v8::HandleScope<'a>,
+        promise: v8::Local<'a, v8::Promise>,
+    ) -> PyResult<PyObject> {
+        Self::create(py, scope, promise, false)
+    }
+
+    pub fn wrap_step<'a>(
+        py: Python<'_>,
+        scope: &mut v8::HandleScope<'a>,
+        promise: v8::Local<'a, v8::Promise>,
+    ) -> PyResult<PyObject> {
+        Self::create(py, scope, promise, true)
+    }
+
+    fn create<'a>(
+        py: Python<'_>,
+        scope: &mut v8::HandleScope<'a>,
+        promise: v8::Local<'a, v8::Promise>,
+        step: bool,
+    ) -> PyResult<PyObject> {
+        let runtime = RuntimeRef::get(scope)?;
+        // 添加空的 catch，拒绝由 Python 侧处理，不作为未处理的拒绝报告
+        let noop = v8::Function::new(scope, |_: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, _: v8::ReturnValue| {})
+            .ok_or_else(|| JsError::RuntimeError("Failed to create function".to_string()))?;
+        promise.catch(scope, noop);
+        let promise = v8::Global::new(scope, promise);
+        let inner = ThreadBound::new(PromiseRef { promise, runtime });
+        Ok(Py::new(py, Self { inner, step })?.into_any())
+    }
+
+    // 属于当前 runtime 时还原为原来的 Promise
+    pub fn local<'a>(&self, scope: &mut v8::HandleScope<'a>) -> Option<v8::Local<'a, v8::Value>> {
+        let inner = self.inner.get().filter(|inner| RuntimeRef::owns(scope, &inner.runtime))?;
+        Some(v8::Local::new(scope, &inner.promise).into())
+    }
+}
+
+impl PromiseRef {
+    fn runtime(&self) -> PyResult<Arc<RuntimeCell>> {
+        self.runtime.upgrade().ok_or_else(|| PyRuntimeError::new_err("JsRuntime has been dropped"))
+    }
+
+    fn done(&self) -> PyResult<bool> {
+        let runtime = self.runtime()?;
+        let mut runtime = runtime.enter()?;
+        Ok(runtime.with_scope(|scope| v8::Local::new(scope, &self.promise).state() != v8::PromiseState::Pending))
+    }
+
+    // 已兑现时返回转换后的值，已拒绝时返回对应的 Python 异常，未完成时返回 None
+    fn settled(&self, py: Python<'_>, runtime: &mut RuntimeGuard, step: bool) -> PyResult<Option<PyObject>> {
+        runtime.with_scope(|scope| {
+            let promise = v8::Local::new(scope, &self.promise);
+            match promise.state() {
+                v8::PromiseState::Pending => Ok(None),
+                v8::PromiseState::Fulfilled if step => {
+                    let result = v8::Local::<v8::Object>::try_from(promise.result(scope))
+                        .map_err(|_| PyTypeError::new_err("Iterator result is not an object"))?;
+                    if get_property(scope, result, "done").is_some_and(|done| done.boolean_value(scope)) {
+                        return Err(PyStopAsyncIteration::new_err(()));
+                    }
+                    match get_property(scope, result, "value") {
+                        Some(value) => js_to_py(py, scope, value).map(Some),
+                        None => Ok(Some(py.None())),
+                    }
+                }
+                v8::PromiseState::Fulfilled => {
+                    let value = promise.result(scope);
+                    js_to_py(py, scope, value).map(Some)
+                }
+                v8::PromiseState::Rejected => {
+                    let reason = promise.result(scope);
+                    Err(js_error_to_py(py, scope, reason))
+                }
+            }
+        })
+    }
+
+    // 嵌套调用中不能驱动事件循环，只能取回已完成的结果
+    fn result(&self, py: Python<'_>, step: bool) -> PyResult<PyObject> {
+        let runtime = self.runtime()?;
+        let mut guard = runtime.enter()?;
+        if let Some(value) = self.settled(py, &mut guard, step)? {
+            return Ok(value);
+        }
+        if guard.nested() {
+            return Err(JsError::RuntimeError("Cannot wait for a pending Promise inside a nested call".to_string()).into());
+        }
+        let value = guard.with_scope(|scope| {
+            let promise = v8::Local::new(scope, &self.promise);
+            v8::Global::new(scope, v8::Local::<v8::Value>::from(promise))
+        });
+        let runtime = guard.runtime()?;
+        let resolve = runtime.resolve(value);
+        let outcome = block_on_without_gil(runtime.with_event_loop_promise(resolve, PollEventLoopOptions::default()));
+        match self.settled(py, &mut guard, step)? {
+            Some(value) => Ok(value),
+            None => Err(JsError::ExecutionError(match outcome {
+                Err(e) => e.to_string(),
+                Ok(_) => "Promise did not settle".to_string(),
+            }).into()),
+        }
+    }
+
+    // 非阻塞地轮询一次 JS 事件循环，返回已完成的结果；未完成时返回 None
+    fn poll(&self, py: Python<'_>, step: bool, waker: &Waker) -> PyResult<Option<PyObject>> {
+        let runtime = self.runtime()?;
+        let mut guard = runtime.enter()?;
+        let poll = guard.runtime()?.poll_event_loop(&mut Context::from_waker(waker), PollEventLoopOptions::default());
+        if let Some(value) = self.settled(py, &mut guard, step)? {
+            return Ok(Some(value));
+        }
+        match poll {
+            Poll::Ready(Err(e)) => Err(JsError::ExecutionError(e.to_string()).into()),
+            Poll::Ready(Ok(())) => Err(JsError::ExecutionError("Promise will never settle: the event loop has no pending work".to_string()).into()),
+            Poll::Pending => Ok(None),
+        }
+    }
+}
+
+#[pymethods]
+impl JsPromise {
+    fn done(&self) -> PyResult<bool> {
+        self.inner.run(|inner| inner.done())?
+    }
+
+    // 同步等待 Promise 完成并返回结果
+    fn result(&self) -> PyResult<PyObject> {
+        let step = self.step;
+        self.inner.run(move |inner| Python::with_gil(|py| inner.result(py, step)))?
+    }
+
+    fn __await__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    // 每一步非阻塞地轮询一次 JS 事件循环；未完成时交出一个 asyncio Future，
+    // 事件循环被唤醒时该 Future 完成，asyncio 随即再次调用本方法
+    fn __next__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let event_loop = py.import("asyncio")?.call_method0("get_running_loop").ok();
+        let future = match &event_loop {
+            Some(event_loop) => Some(event_loop.call_method0("create_future")?),
+            None => None,
+        };
+        let waker = match (&event_loop, &future) {
+            (Some(event_loop), Some(future)) => Waker::from(Arc::new(FutureWaker {
+                event_loop: event_loop.clone().unbind(),
+                future: future.clone().unbind(),
+                woken: AtomicBool::new(false),
+            })),
+            _ => Waker::noop().clone(),
+        };
+        let step = self.step;
+        if let Some(value) = self.inner.run(move |inner| Python::with_gil(|py| inner.poll(py, step, &waker)))?? {
+            return Err(PyStopIteration::new_err((value,)));
+        }
+        // 没有运行中的 asyncio 事件循环时（例如手动迭代）直接让出
+        match future {
+            Some(future) => {
+                future.setattr("_asyncio_future_blocking", true)?;
+                Ok(future.unbind())
+            }
+            None => Ok(py.None()),
+        }
+    }
+}
+
+// JS 事件循环的唤醒器：在对应 asyncio 事件循环中完成 Future（只完成一次）
+struct FutureWaker {
+    event_loop: PyObject,
+    future: PyObject,
+    woken: AtomicBool,
+}
+
+impl Wake for FutureWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if self.woken.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        Python::with_gil(|py| {
+            let future = self.future.clone_ref(py);
+            // 等待的任务可能已被取消，只在 Future 未完成时设置结果
+            let callback = PyCFunction::new_closure(py, None, None, move |args, _| -> PyResult<()> {
+                let future = future.bind(args.py());
+                if !future.call_method0("done")?.is_truthy()? {
+                    future.call_method1("set_result", (args.py().None(),))?;
+                }
+                Ok(())
+            });
+            // 事件循环已关闭时忽略
+            if let Ok(callback) = callback {
+                let _ = self.event_loop.call_method1(py, "call_soon_threadsafe", (callback,));
+            }
+        });
+    }
+}
@@ -0,0 +1,587 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyAttributeError, PyLookupError};
+use pyo3::types::{PyBool, PyBytes, PyDate, PyFloat, PyInt, PyMapping, PySequence, PyString, PyTime, PyTuple, PyType};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::rc::Rc;
+use crate::engine::py_async;
+use crate::types::convert::{js_to_py, py_to_js};
+use crate::types::error::JsError;
+use crate::types::registry::ConverterRegistry;
+use crate::types::symbol::JsSymbol;
+
+// 以 V8 属性拦截器实现的 Python 对象代理：读写、in、枚举与方法调用实时转发到 Python 对象
+// mapping 的命名属性对应键，其余对象对应属性；数字索引对应序列的 __getitem__
+// Python 类转为 JS 构造函数，new 出的实例同样是代理
+
+// 代理对象上保存 Python 对象指针的私有属性名
+const PROXY_KEY: &str = "py_js_runtime#object";
+
+// 缓存在 isolate slot 中的代理对象模板
+struct ProxyTemplate(v8::Global<v8::ObjectTemplate>);
+
+// 已转为 JS 构造函数的 Python 类及其函数模板，同一个类始终对应同一个构造函数
+// 以类对象地址为键，并持有类对象的引用，因此地址不会被复用
+#[derive(Default)]
+struct ClassTemplates {
+    templates: HashMap<usize, (PyObject, v8::Global<v8::FunctionTemplate>)>,
+    // 实例类型 -> MRO 中最近的有模板的类；新增模板时清空
+    instances: HashMap<usize, (PyObject, Option<usize>)>,
+}
+
+enum Key {
+    Name(String),
+    Index(u32),
+}
+
+// 创建包装 Python 对象的代理
+// 实例所属的类（或其基类）已注册为构造函数时使用该类的实例模板，以保证 instanceof 成立
+pub fn wrap<'a>(scope: &mut v8::HandleScope<'a>, obj: &Bound<'_, PyAny>) -> PyResult<v8::Local<'a, v8::Object>> {
+    let template = match instance_class(scope, obj)? {
+        Some(class) => class.instance_template(scope),
+        None => template(scope),
+    };
+    let object = template.new_instance(scope)
+        .ok_or_else(|| PyErr::from(JsError::RuntimeError("Failed to create proxy".to_string())))?;
+    attach(scope, object, obj.clone().unbind());
+    Ok(object)
+}
+
+// Python 值转为 JS：标量按 py_to_js 复制，类转为构造函数，awaitable 转为 Promise，可调用对象转为函数，其余对象包装为代理
+pub fn to_js<'a>(scope: &mut v8::HandleScope<'a>, obj: &Bound<'_, PyAny>) -> PyResult<v8::Local<'a, v8::Value>> {
+    if is_value_type(obj) {
+        return py_to_js(scope, obj);
+    }
+    if let Some(registry) = ConverterRegistry::get(scope) {
+        if registry.find_py(obj)?.is_some() {
+            return py_to_js(scope, obj);
+        }
+    }
+    if let Ok(class) = obj.downcast::<PyType>() {
+        return constructor(scope, class).map(Into::into);
+    }
+    // 协程等 awaitable 转为 Promise，因此协程函数在 JS 中表现为返回 Promise 的函数
+    if py_async::is_awaitable(obj)? {
+        return py_async::awaitable_to_promise(scope, obj);
+    }
+    let proxy = wrap(scope, obj)?;
+    if !obj.is_callable() {
+        return Ok(proxy.into());
+    }
+    let func = v8::Function::builder(call_function)
+        .data(proxy.into())
+        .build(scope)
+        .ok_or_else(|| PyErr::from(JsError::RuntimeError("Failed to create function".to_string())))?;
+    if let Some(name) = obj.getattr("__name__").ok().and_then(|name| name.extract::<String>().ok()) {
+        if let Some(name) = v8::String::new(scope, &name) {
+            func.set_name(name);
+        }
+    }
+    Ok(func.into())
+}
+
+// 若 JS 值为代理，取回其包装的 Python 对象
+pub fn unwrap(py: Python<'_>, scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> Option<PyObject> {
+    let object = v8::Local::<v8::Object>::try_from(value).ok()?;
+    let key = private_key(scope);
+    let data = object.get_private(scope, key)?;
+    let external = v8::Local::<v8::External>::try_from(data).ok()?;
+    // 指针在代理对象被回收前一直有效
+    let obj = unsafe { &*(external.value() as *const PyObject) };
+    Some(obj.clone_ref(py))
+}
+
+// 在模板上安装命名与索引属性拦截器
+pub fn configure(template: &v8::ObjectTemplate) {
+    template.set_named_property_handler(
+        v8::NamedPropertyHandlerConfiguration::new()
+            .getter(named_getter)
+            .setter(named_setter)
+            .query(named_query)
+            .deleter(named_deleter)
+            .enumerator(named_enumerator)
+            .flags(v8::PropertyHandlerFlags::ONLY_INTERCEPT_STRINGS),
+    );
+    template.set_indexed_property_handler(
+        v8::IndexedPropertyHandlerConfiguration::new()
+            .getter(indexed_getter)
+            .setter(indexed_setter)
+            .query(indexed_query)
+            .deleter(indexed_deleter)
+            .enumerator(indexed_enumerator),
+    );
+}
+
+// 将 Python 对象关联到 JS 对象，JS 对象被回收时释放引用
+pub fn attach(scope: &mut v8::HandleScope, object: v8::Local<v8::Object>, obj: PyObject) {
+    let pointer = Box::into_raw(Box::new(obj));
+    let external = v8::External::new(scope, pointer as *mut c_void);
+    let key = private_key(scope);
+    object.set_private(scope, key, external.into());
+
+    let weak = Rc::new(RefCell::new(None));
+    let handle = weak.clone();
+    let finalizer = v8::Weak::with_guaranteed_finalizer(scope, object, Box::new(move || {
+        handle.borrow_mut().take();
+        drop(unsafe { Box::from_raw(pointer) });
+    }));
+    *weak.borrow_mut() = Some(finalizer);
+}
+
+// Python 类对应的 JS 构造函数：new 时实例化该类，实例的属性与方法经拦截器转发
+pub fn constructor<'a>(scope: &mut v8::HandleScope<'a>, class: &Bound<'_, PyType>) -> PyResult<v8::Local<'a, v8::Function>> {
+    let template = class_template(scope, class)?;
+    template.get_function(scope)
+        .ok_or_else(|| JsError::RuntimeError("Failed to create constructor".to_string()).into())
+}
+
+// 取得类的函数模板，不存在时创建
+// 创建时先为 MRO 中最近的自定义基类创建模板并继承，因此无论基类之后是否注册、何时注册，instanceof 基类都成立
+fn class_template<'a>(scope: &mut v8::HandleScope<'a>, class: &Bound<'_, PyType>) -> PyResult<v8::Local<'a, v8::FunctionTemplate>> {
+    if let Some(template) = find_template(scope, class.as_ptr() as usize) {
+        return Ok(template);
+    }
+    let parent = match user_base(class)? {
+        Some(base) => Some(class_template(scope, &base)?),
+        None => None,
+    };
+    let data = wrap(scope, class.as_any())?;
+    let template = v8::FunctionTemplate::builder(construct)
+        .data(data.into())
+        .build(scope);
+    if let Some(name) = v8::String::new(scope, &class.name()?.to_string()) {
+        template.set_class_name(name);
+    }
+    configure(&template.instance_template(scope));
+    if let Some(parent) = parent {
+        template.inherit(parent);
+    }
+    let global = v8::Global::new(scope, template);
+    if scope.get_slot::<ClassTemplates>().is_none() {
+        scope.set_slot(ClassTemplates::default());
+    }
+    if let Some(classes) = scope.get_slot_mut::<ClassTemplates>() {
+        classes.templates.insert(class.as_ptr() as usize, (class.clone().into_any().unbind(), global));
+        classes.instances.clear();
+    }
+    Ok(template)
+}
+
+// MRO 中第一个非内置的基类；内置类型（object、dict 等）不建模板，以免普通对象的代理改用类模板
+fn user_base<'py>(class: &Bound<'py, PyType>) -> PyResult<Option<Bound<'py, PyType>>> {
+    for base in class.mro().iter().skip(1) {
+        let base = base.downcast_into::<PyType>()?;
+        if base.module()?.to_str()? != "builtins" {
+            return Ok(Some(base));
+        }
+    }
+    Ok(None)
+}
+
+fn find_template<'a>(scope: &mut v8::HandleScope<'a>, class: usize) -> Option<v8::Local<'a, v8::FunctionTemplate>> {
+    let template = scope.get_slot::<ClassTemplates>()?
+        .templates
+        .get(&class)
+        .map(|(_, template)| template.clone())?;
+    Some(v8::Local::new(scope, template))
+}
+
+// 按 MRO 顺序查找对象所属的有模板的类，结果按类型缓存
+fn instance_class<'a>(scope: &mut v8::HandleScope<'a>, obj: &Bound<'_, PyAny>) -> PyResult<Option<v8::Local<'a, v8::FunctionTemplate>>> {
+    let Some(classes) = scope.get_slot::<ClassTemplates>() else {
+        return Ok(None);
+    };
+    let ty = obj.get_type();
+    let key = ty.as_ptr() as usize;
+    let class = match classes.instances.get(&key) {
+        Some((_, class)) => *class,
+        None => {
+            let mut found = None;
+            for class in ty.mro().iter() {
+                if classes.templates.contains_key(&(class.as_ptr() as usize)) {
+                    found = Some(class.as_ptr() as usize);
+                    break;
+                }
+            }
+            if let Some(classes) = scope.get_slot_mut::<ClassTemplates>() {
+                classes.instances.insert(key, (ty.into_any().unbind(), found));
+            }
+            found
+        }
+    };
+    Ok(class.and_then(|class| find_template(scope, class)))
+}
+
+fn template<'a>(scope: &mut v8::HandleScope<'a>) -> v8::Local<'a, v8::ObjectTemplate> {
+    let cached = scope.get_slot::<ProxyTemplate>().map(|template| template.0.clone());
+    if let Some(template) = cached {
+        return v8::Local::new(scope, template);
+    }
+    let template = v8::ObjectTemplate::new(scope);
+    configure(&template);
+    let global = v8::Global::new(scope, template);
+    scope.set_slot(ProxyTemplate(global));
+    template
+}
+
+fn private_key<'a>(scope: &mut v8::HandleScope<'a>) -> v8::Local<'a, v8::Private> {
+    let name = v8::String::new(scope, PROXY_KEY).unwrap();
+    v8::Private::for_api(scope, Some(name))
+}
+
+fn is_value_type(obj: &Bound<'_, PyAny>) -> bool {
+    obj.is_none()
+        || obj.is_instance_of::<PyBool>()
+        || obj.is_instance_of::<PyInt>()
+        || obj.is_instance_of::<PyFloat>()
+        || obj.is_instance_of::<PyString>()
+        || obj.is_instance_of::<PyBytes>()
+        || obj.is_instance_of::<PyDate>()
+        || obj.is_instance_of::<PyTime>()
+        || obj.is_instance_of::<JsSymbol>()
+}
+
+fn is_mapping(obj: &Bound<'_, PyAny>) -> bool {
+    obj.downcast::<PyMapping>().is_ok()
+}
+
+fn is_sequence(obj: &Bound<'_, PyAny>) -> bool {
+    obj.downcast::<PySequence>().is_ok() && !obj.is_instance_of::<PyString>()
+}
+
+// 属性或键不存在（AttributeError、KeyError、IndexError）时返回 None，交由 JS 默认行为处理
+fn missing<T>(py: Python<'_>, result: PyResult<T>) -> PyResult<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_instance_of::<PyAttributeError>(py) || e.is_instance_of::<PyLookupError>(py) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn get<'py>(obj: &Bound<'py, PyAny>, key: &Key) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = obj.py();
+    match key {
+        Key::Name(name) if is_mapping(obj) => missing(py, obj.get_item(name)),
+        Key::Name(name) => match missing(py, obj.getattr(name))? {
+            // 序列没有 length 属性时以 len() 代替
+            None if name == "length" && is_sequence(obj) => Ok(Some(obj.len()?.into_pyobject(py)?.into_any())),
+            value => Ok(value),
+        },
+        Key::Index(index) if is_mapping(obj) => missing(py, obj.get_item(index.to_string())),
+        Key::Index(index) if is_sequence(obj) => missing(py, obj.get_item(index)),
+        Key::Index(_) => Ok(None),
+    }
+}
+
+fn set(obj: &Bound<'_, PyAny>, key: &Key, value: PyObject) -> PyResult<bool> {
+    match key {
+        Key::Name(name) if is_mapping(obj) => obj.set_item(name, value).map(|_| true),
+        Key::Name(name) => obj.setattr(name, value).map(|_| true),
+        Key::Index(index) if is_mapping(obj) => obj.set_item(index.to_string(), value).map(|_| true),
+        Key::Index(index) if is_sequence(obj) => obj.set_item(index, value).map(|_| true),
+        Key::Index(_) => Ok(false),
+    }
+}
+
+fn has(obj: &Bound<'_, PyAny>, key: &Key) -> PyResult<bool> {
+    match key {
+        Key::Name(name) if is_mapping(obj) => obj.contains(name),
+        Key::Name(name) => obj.hasattr(name),
+        Key::Index(index) if is_mapping(obj) => obj.contains(index.to_string()),
+        Key::Index(index) if is_sequence(obj) => Ok((*index as usize) < obj.len()?),
+        Key::Index(_) => Ok(false),
+    }
+}
+
+fn delete(obj: &Bound<'_, PyAny>, key: &Key) -> PyResult<Option<()>> {
+    let py = obj.py();
+    match key {
+        Key::Name(name) if is_mapping(obj) => missing(py, obj.del_item(name)),
+        Key::Name(name) => missing(py, obj.delattr(name)),
+        Key::Index(index) if is_mapping(obj) => missing(py, obj.del_item(index.to_string())),
+        Key::Index(index) if is_sequence(obj) => missing(py, obj.del_item(index)),
+        Key::Index(_) => Ok(None),
+    }
+}
+
+// 命名属性枚举：mapping 取字符串键，序列不枚举，其余对象取不以下划线开头的属性
+fn names(obj: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    if is_mapping(obj) {
+        let keys = obj.call_method0("keys")?;
+        return Ok(keys.try_iter()?.filter_map(|key| key.ok()?.extract::<String>().ok()).collect());
+    }
+    if is_sequence(obj) {
+        return Ok(Vec::new());
+    }
+    let names: Vec<String> = obj.dir()?.extract()?;
+    Ok(names.into_iter().filter(|name| !name.starts_with('_')).collect())
+}
+
+fn target(py: Python<'_>, scope: &mut v8::HandleScope, args: &v8::PropertyCallbackArguments) -> Option<PyObject> {
+    unwrap(py, scope, args.holder().into())
+}
+
+// Python 异常以 JS Error 抛出
+pub fn throw(scope: &mut v8::HandleScope, err: PyErr) {
+    let message = v8::String::new(scope, &err.to_string()).unwrap_or_else(|| v8::String::empty(scope));
+    let exception = v8::Exception::error(scope, message);
+    scope.throw_exception(exception);
+}
+
+fn intercept_get(
+    scope: &mut v8::HandleScope,
+    key: Key,
+    args: v8::PropertyCallbackArguments,
+    mut rv: v8::ReturnValue,
+) -> v8::Intercepted {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return v8::Intercepted::No;
+        };
+        let result = get(obj.bind(py), &key)
+            .and_then(|value| value.map(|value| to_js(scope, &value)).transpose());
+        match result {
+            Ok(Some(value)) => rv.set(value),
+            Ok(None) => return v8::Intercepted::No,
+            Err(e) => throw(scope, e),
+        }
+        v8::Intercepted::Yes
+    })
+}
+
+fn intercept_set<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: Key,
+    value: v8::Local<'s, v8::Value>,
+    args: v8::PropertyCallbackArguments<'s>,
+) -> v8::Intercepted {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return v8::Intercepted::No;
+        };
+        let result = js_to_py(py, scope, value).and_then(|value| set(obj.bind(py), &key, value));
+        match result {
+            Ok(true) => v8::Intercepted::Yes,
+            Ok(false) => v8::Intercepted::No,
+            Err(e) => {
+                throw(scope, e);
+                v8::Intercepted::Yes
+            }
+        }
+    })
+}
+
+fn intercept_query(
+    scope: &mut v8::HandleScope,
+    key: Key,
+    args: v8::PropertyCallbackArguments,
+    mut rv: v8::ReturnValue<v8::Integer>,
+) -> v8::Intercepted {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return v8::Intercepted::No;
+        };
+        match has(obj.bind(py), &key) {
+            Ok(true) => rv.set_int32(v8::PropertyAttribute::NONE.as_u32() as i32),
+            Ok(false) => return v8::Intercepted::No,
+            Err(e) => throw(scope, e),
+        }
+        v8::Intercepted::Yes
+    })
+}
+
+fn intercept_delete(
+    scope: &mut v8::HandleScope,
+    key: Key,
+    args: v8::PropertyCallbackArguments,
+    mut rv: v8::ReturnValue<v8::Boolean>,
+) -> v8::Intercepted {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return v8::Intercepted::No;
+        };
+        match delete(obj.bind(py), &key) {
+            Ok(Some(())) => rv.set_bool(true),
+            Ok(None) => return v8::Intercepted::No,
+            Err(e) => throw(scope, e),
+        }
+        v8::Intercepted::Yes
+    })
+}
+
+fn named_getter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: v8::Local<'s, v8::Name>,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue,
+) -> v8::Intercepted {
+    let key = Key::Name(key.to_rust_string_lossy(scope));
+    intercept_get(scope, key, args, rv)
+}
+
+fn named_setter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: v8::Local<'s, v8::Name>,
+    value: v8::Local<'s, v8::Value>,
+    args: v8::PropertyCallbackArguments<'s>,
+    _rv: v8::ReturnValue<()>,
+) -> v8::Intercepted {
+    let key = Key::Name(key.to_rust_string_lossy(scope));
+    intercept_set(scope, key, value, args)
+}
+
+fn named_query<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: v8::Local<'s, v8::Name>,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue<v8::Integer>,
+) -> v8::Intercepted {
+    let key = Key::Name(key.to_rust_string_lossy(scope));
+    intercept_query(scope, key, args, rv)
+}
+
+fn named_deleter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: v8::Local<'s, v8::Name>,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue<v8::Boolean>,
+) -> v8::Intercepted {
+    let key = Key::Name(key.to_rust_string_lossy(scope));
+    intercept_delete(scope, key, args, rv)
+}
+
+fn named_enumerator<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::PropertyCallbackArguments<'s>,
+    mut rv: v8::ReturnValue<v8::Array>,
+) {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return;
+        };
+        match names(obj.bind(py)) {
+            Ok(names) => {
+                let names = names.iter()
+                    .filter_map(|name| v8::String::new(scope, name).map(Into::into))
+                    .collect::<Vec<_>>();
+                rv.set(v8::Array::new_with_elements(scope, &names));
+            }
+            Err(e) => throw(scope, e),
+        }
+    })
+}
+
+fn indexed_getter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    index: u32,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue,
+) -> v8::Intercepted {
+    intercept_get(scope, Key::Index(index), args, rv)
+}
+
+fn indexed_setter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    index: u32,
+    value: v8::Local<'s, v8::Value>,
+    args: v8::PropertyCallbackArguments<'s>,
+    _rv: v8::ReturnValue<()>,
+) -> v8::Intercepted {
+    intercept_set(scope, Key::Index(index), value, args)
+}
+
+fn indexed_query<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    index: u32,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue<v8::Integer>,
+) -> v8::Intercepted {
+    intercept_query(scope, Key::Index(index), args, rv)
+}
+
+fn indexed_deleter<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    index: u32,
+    args: v8::PropertyCallbackArguments<'s>,
+    rv: v8::ReturnValue<v8::Boolean>,
+) -> v8::Intercepted {
+    intercept_delete(scope, Key::Index(index), args, rv)
+}
+
+// 索引枚举：序列取 0..len
+fn indexed_enumerator<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::PropertyCallbackArguments<'s>,
+    mut rv: v8::ReturnValue<v8::Array>,
+) {
+    Python::with_gil(|py| {
+        let Some(obj) = target(py, scope, &args) else {
+            return;
+        };
+        let obj = obj.bind(py);
+        if !is_sequence(obj) {
+            return;
+        }
+        match obj.len() {
+            Ok(len) => {
+                let indices = (0..len as u32)
+                    .map(|i| v8::Integer::new_from_unsigned(scope, i).into())
+                    .collect::<Vec<_>>();
+                rv.set(v8::Array::new_with_elements(scope, &indices));
+            }
+            Err(e) => throw(scope, e),
+        }
+    })
+}
+
+// 构造函数：data 为包装 Python 类的代理，实例关联到 new 创建的 this 上
+fn construct<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    _rv: v8::ReturnValue,
+) {
+    Python::with_gil(|py| {
+        let Some(class) = unwrap(py, scope, args.data()) else {
+            return;
+        };
+        if args.new_target().is_undefined() {
+            let message = format!("Class constructor {} cannot be invoked without 'new'", class.bind(py));
+            let message = v8::String::new(scope, &message).unwrap_or_else(|| v8::String::empty(scope));
+            let exception = v8::Exception::type_error(scope, message);
+            scope.throw_exception(exception);
+            return;
+        }
+        let result = (0..args.length())
+            .map(|i| js_to_py(py, scope, args.get(i)))
+            .collect::<PyResult<Vec<_>>>()
+            .and_then(|py_args| class.bind(py).call1(PyTuple::new(py, py_args)?));
+        match result {
+            Ok(instance) => attach(scope, args.this(), instance.unbind()),
+            Err(e) => throw(scope, e),
+        }
+    })
+}
+
+// 代理函数：data 为包装可调用对象的代理，参数经 js_to_py 转换，返回值经 to_js 转换
+fn call_function<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    Python::with_gil(|py| {
+        let Some(func) = unwrap(py, scope, args.data()) else {
+            return;
+        };
+        let result = (0..args.length())
+            .map(|i| js_to_py(py, scope, args.get(i)))
+            .collect::<PyResult<Vec<_>>>()
+            .and_then(|py_args| func.bind(py).call1(PyTuple::new(py, py_args)?))
+            .and_then(|result| to_js(scope, &result));
+        match result {
+            Ok(value) => rv.set(value),
+            Err(e) => throw(scope, e),
+        }
+    })
+}
@@ -0,0 +1,89 @@
+use deno_core::v8;
+use pyo3::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::engine::v8engine::exception_message;
+use crate::types::error::JsError;
+
+// JS -> Python：先在 JS 中调用 js（可选），再按内置规则转换，最后调用 Python 的 to_py（可选）
+pub struct JsConverter {
+    pub js: Option<v8::Global<v8::Function>>,
+    pub to_py: Option<PyObject>,
+}
+
+// Python -> JS：先调用 Python 的 to_js（可选），再按内置规则转换，最后在 JS 中调用 js（可选）
+pub struct PyConverter {
+    pub py_type: PyObject,
+    pub to_js: Option<PyObject>,
+    pub js: Option<v8::Global<v8::Function>>,
+}
+
+// 用户注册的类型转换器，保存在 isolate 的 slot 中，js_to_py/py_to_js 优先查找
+#[derive(Default)]
+pub struct ConverterRegistry {
+    // 按 JS 构造函数名索引
+    js: RefCell<HashMap<String, Rc<JsConverter>>>,
+    // 按注册顺序以 isinstance 匹配
+    py: RefCell<Vec<Rc<PyConverter>>>,
+}
+
+impl ConverterRegistry {
+    pub fn get(isolate: &v8::Isolate) -> Option<Rc<Self>> {
+        isolate.get_slot::<Rc<Self>>().cloned()
+    }
+
+    pub fn install(self: Rc<Self>, isolate: &mut v8::Isolate) {
+        isolate.set_slot(self);
+    }
+
+    pub fn register_js(&self, constructor_name: String, converter: JsConverter) {
+        self.js.borrow_mut().insert(constructor_name, Rc::new(converter));
+    }
+
+    pub fn register_py(&self, converter: PyConverter) {
+        self.py.borrow_mut().push(Rc::new(converter));
+    }
+
+    // 查找 JS 对象构造函数名对应的转换器
+    pub fn find_js(&self, scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> Option<Rc<JsConverter>> {
+        let js = self.js.borrow();
+        if js.is_empty() || !value.is_object() {
+            return None;
+        }
+        let name = value.to_object(scope)?.get_constructor_name().to_rust_string_lossy(scope);
+        js.get(&name).cloned()
+    }
+
+    // 查找第一个 isinstance 匹配的转换器
+    pub fn find_py(&self, obj: &Bound<'_, PyAny>) -> PyResult<Option<Rc<PyConverter>>> {
+        // isinstance 可能执行 Python 代码（__instancecheck__），遍历副本
+        let converters = self.py.borrow().clone();
+        for converter in converters {
+            if obj.is_instance(converter.py_type.bind(obj.py()))? {
+                return Ok(Some(converter));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// 调用 JS 转换函数，异常转为 Python 异常
+pub fn call_converter<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    func: &v8::Global<v8::Function>,
+    arg: v8::Local<'a, v8::Value>,
+) -> PyResult<v8::Local<'a, v8::Value>> {
+    let mut try_catch = v8::TryCatch::new(scope);
+    let func = v8::Local::new(&mut try_catch, func);
+    let recv = v8::undefined(&mut try_catch).into();
+    match func.call(&mut try_catch, recv, &[arg]) {
+        Some(result) => Ok(result),
+        None => {
+            let message = try_catch.exception()
+                .map(|exception| exception_message(&mut try_catch, exception))
+                .unwrap_or_else(|| "Unknown JavaScript error".to_string());
+            Err(JsError::ExecutionError(format!("Converter failed: {}", message)).into())
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use pyo3::prelude::*;
+
+// JS Symbol 在 Python 侧的表示，仅保留描述
+// 传回 JS 时会创建一个新的 Symbol，与原 Symbol 不相等
+#[pyclass(name = "JsSymbol", frozen)]
+pub struct JsSymbol {
+    #[pyo3(get)]
+    description: Option<String>,
+}
+
+impl JsSymbol {
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+#[pymethods]
+impl JsSymbol {
+    #[new]
+    #[pyo3(signature = (description=None))]
+    pub fn new(description: Option<String>) -> Self {
+        Self { description }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Symbol({})", self.description.as_deref().unwrap_or(""))
+    }
+}
@@ -0,0 +1,411 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::types::{PyBool, PyByteArray, PyBytes, PyDate, PyDateTime, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PyMemoryView, PySet, PyString, PyTime, PyTuple};
+use std::io::{self, Read, Write};
+use crate::types::convert::{array_type, to_plain_value, MS_PER_DAY, UNIX_EPOCH_ORDINAL};
+use crate::types::map::JsMap;
+
+// 跨进程传递的值：tag 字节后跟内容，长度与整数使用 LEB128 变长编码（整数先经 zigzag）
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    None,
+    Bool(bool),
+    Int(i64),
+    // 超出 i64 的整数以十进制字符串传递
+    BigInt(String),
+    Float(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+    Tuple(Vec<Value>),
+    // set 与 frozenset 都还原为 set
+    Set(Vec<Value>),
+    // POSIX 时间戳（秒），与 JS Date 的转换规则一致
+    DateTime(f64),
+    // JsMap 的条目
+    Map(Vec<(Value, Value)>),
+    // 带元素类型的缓冲区：容器类型、array 模块的类型码、原始字节
+    Buffer(BufferKind, String, Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferKind {
+    ByteArray,
+    Array,
+    MemoryView,
+    Numpy,
+}
+
+const TAG_NONE: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_BIGINT: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_STR: u8 = 6;
+const TAG_BYTES: u8 = 7;
+const TAG_LIST: u8 = 8;
+const TAG_DICT: u8 = 9;
+const TAG_TUPLE: u8 = 10;
+const TAG_SET: u8 = 11;
+const TAG_DATETIME: u8 = 12;
+const TAG_MAP: u8 = 13;
+const TAG_BUFFER: u8 = 14;
+
+// 可以原样重建为 array.array / memoryview 的元素类型码
+const BUFFER_TYPECODES: &str = "bBhHiIlLqQfd";
+
+impl Value {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::None => out.push(TAG_NONE),
+            Self::Bool(false) => out.push(TAG_FALSE),
+            Self::Bool(true) => out.push(TAG_TRUE),
+            Self::Int(value) => {
+                out.push(TAG_INT);
+                write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+            }
+            Self::BigInt(digits) => {
+                out.push(TAG_BIGINT);
+                write_bytes(out, digits.as_bytes());
+            }
+            Self::Float(value) => {
+                out.push(TAG_FLOAT);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            Self::Str(value) => {
+                out.push(TAG_STR);
+                write_bytes(out, value.as_bytes());
+            }
+            Self::Bytes(value) => {
+                out.push(TAG_BYTES);
+                write_bytes(out, value);
+            }
+            Self::List(items) => encode_items(out, TAG_LIST, items),
+            Self::Tuple(items) => encode_items(out, TAG_TUPLE, items),
+            Self::Set(items) => encode_items(out, TAG_SET, items),
+            Self::Dict(entries) => encode_entries(out, TAG_DICT, entries),
+            Self::Map(entries) => encode_entries(out, TAG_MAP, entries),
+            Self::DateTime(timestamp) => {
+                out.push(TAG_DATETIME);
+                out.extend_from_slice(&timestamp.to_le_bytes());
+            }
+            Self::Buffer(kind, typecode, bytes) => {
+                out.push(TAG_BUFFER);
+                out.push(*kind as u8);
+                write_bytes(out, typecode.as_bytes());
+                write_bytes(out, bytes);
+            }
+        }
+    }
+
+    pub fn decode(input: &mut &[u8]) -> io::Result<Self> {
+        let tag = read_byte(input)?;
+        Ok(match tag {
+            TAG_NONE => Self::None,
+            TAG_FALSE => Self::Bool(false),
+            TAG_TRUE => Self::Bool(true),
+            TAG_INT => {
+                let raw = read_varint(input)?;
+                Self::Int(((raw >> 1) as i64) ^ -((raw & 1) as i64))
+            }
+            TAG_BIGINT => Self::BigInt(read_string(input)?),
+            TAG_FLOAT => Self::Float(read_f64(input)?),
+            TAG_STR => Self::Str(read_string(input)?),
+            TAG_BYTES => {
+                let len = read_varint(input)? as usize;
+                Self::Bytes(take(input, len)?.to_vec())
+            }
+            TAG_LIST => Self::List(decode_items(input)?),
+            TAG_TUPLE => Self::Tuple(decode_items(input)?),
+            TAG_SET => Self::Set(decode_items(input)?),
+            TAG_DICT => Self::Dict(decode_entries(input)?),
+            TAG_MAP => Self::Map(decode_entries(input)?),
+            TAG_DATETIME => Self::DateTime(read_f64(input)?),
+            TAG_BUFFER => {
+                let kind = match read_byte(input)? {
+                    0 => BufferKind::ByteArray,
+                    1 => BufferKind::Array,
+                    2 => BufferKind::MemoryView,
+                    3 => BufferKind::Numpy,
+                    kind => return Err(invalid(format!("Unknown buffer kind {}", kind))),
+                };
+                let typecode = read_string(input)?;
+                let len = read_varint(input)? as usize;
+                Self::Buffer(kind, typecode, take(input, len)?.to_vec())
+            }
+            _ => return Err(invalid(format!("Unknown value tag {}", tag))),
+        })
+    }
+
+    // 与 isolation="thread" 保持相同的类型：标量、bytes、list/tuple/dict/set、datetime、JsMap 与带元素类型的缓冲区按值复制；
+    // Enum、dataclass、UUID、Decimal 等先按 to_plain_value 转换，date 按 UTC 零点、time 按 ISO 字符串传递（与转为 JS 时一致）。
+    // 代理、函数、JsPromise、迭代器、JsSymbol、re.Pattern 与 JsException 值等引用 isolate 或无法按值表示的对象不能跨进程传递
+    pub fn from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if obj.is_none() {
+            return Ok(Self::None);
+        }
+        if let Some(converted) = to_plain_value(obj)? {
+            return Self::from_py(&converted);
+        }
+        if let Ok(value) = obj.downcast::<PyBool>() {
+            return Ok(Self::Bool(value.is_true()));
+        }
+        if obj.is_instance_of::<PyInt>() {
+            return Ok(match obj.extract::<i64>() {
+                Ok(value) => Self::Int(value),
+                Err(_) => Self::BigInt(obj.str()?.to_string()),
+            });
+        }
+        if let Ok(value) = obj.downcast::<PyFloat>() {
+            return Ok(Self::Float(value.value()));
+        }
+        if let Ok(value) = obj.downcast::<PyString>() {
+            return Ok(Self::Str(value.to_str()?.to_string()));
+        }
+        if let Ok(value) = obj.downcast::<PyBytes>() {
+            return Ok(Self::Bytes(value.as_bytes().to_vec()));
+        }
+        if let Ok(value) = obj.downcast::<PyByteArray>() {
+            return Ok(Self::Buffer(BufferKind::ByteArray, "B".to_string(), value.to_vec()));
+        }
+        if let Some(buffer) = Self::buffer_from_py(obj)? {
+            return Ok(buffer);
+        }
+        // datetime 是 date 的子类，需先判断
+        if let Ok(value) = obj.downcast::<PyDateTime>() {
+            return Ok(Self::DateTime(value.call_method0("timestamp")?.extract()?));
+        }
+        if let Ok(value) = obj.downcast::<PyDate>() {
+            let days = value.call_method0("toordinal")?.extract::<i64>()? - UNIX_EPOCH_ORDINAL;
+            return Ok(Self::DateTime((days * (MS_PER_DAY / 1000)) as f64));
+        }
+        if let Ok(value) = obj.downcast::<PyTime>() {
+            return Ok(Self::Str(value.call_method0("isoformat")?.extract()?));
+        }
+        if let Ok(map) = obj.downcast::<JsMap>() {
+            let entries = map.borrow().entries().iter()
+                .map(|(key, value)| Ok((Self::from_py(key.bind(obj.py()))?, Self::from_py(value.bind(obj.py()))?)))
+                .collect::<PyResult<_>>()?;
+            return Ok(Self::Map(entries));
+        }
+        if let Ok(dict) = obj.downcast::<PyDict>() {
+            let entries = dict.iter()
+                .map(|(key, value)| Ok((Self::from_py(&key)?, Self::from_py(&value)?)))
+                .collect::<PyResult<_>>()?;
+            return Ok(Self::Dict(entries));
+        }
+        if obj.is_instance_of::<PyList>() {
+            return Self::items_from_py(obj).map(Self::List);
+        }
+        if obj.is_instance_of::<PyTuple>() {
+            return Self::items_from_py(obj).map(Self::Tuple);
+        }
+        if obj.is_instance_of::<PySet>() || obj.is_instance_of::<PyFrozenSet>() {
+            return Self::items_from_py(obj).map(Self::Set);
+        }
+        Err(PyTypeError::new_err(format!(
+            "Cannot transfer {} across the process boundary",
+            obj.get_type().name()?
+        )))
+    }
+
+    pub fn into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(match self {
+            Self::None => py.None(),
+            Self::Bool(value) => PyBool::new(py, value).to_owned().into_any().unbind(),
+            Self::Int(value) => value.into_pyobject(py)?.into_any().unbind(),
+            Self::BigInt(digits) => py.get_type::<PyInt>().call1((digits,))?.unbind(),
+            Self::Float(value) => PyFloat::new(py, value).into_any().unbind(),
+            Self::Str(value) => PyString::new(py, &value).into_any().unbind(),
+            Self::Bytes(value) => PyBytes::new(py, &value).into_any().unbind(),
+            Self::List(items) => PyList::new(py, items_into_py(py, items)?)?.into_any().unbind(),
+            Self::Tuple(items) => PyTuple::new(py, items_into_py(py, items)?)?.into_any().unbind(),
+            Self::Set(items) => PySet::new(py, items_into_py(py, items)?)?.into_any().unbind(),
+            Self::Dict(entries) => {
+                let dict = PyDict::new(py);
+                for (key, value) in entries {
+                    dict.set_item(key.into_py(py)?, value.into_py(py)?)?;
+                }
+                dict.into_any().unbind()
+            }
+            Self::Map(entries) => {
+                let entries = entries.into_iter()
+                    .map(|(key, value)| Ok((key.into_py(py)?, value.into_py(py)?)))
+                    .collect::<PyResult<_>>()?;
+                Py::new(py, JsMap::from_entries(entries))?.into_any()
+            }
+            Self::DateTime(timestamp) => PyDateTime::from_timestamp(py, timestamp, None)?.into_any().unbind(),
+            Self::Buffer(kind, typecode, bytes) => match kind {
+                BufferKind::ByteArray => PyByteArray::new(py, &bytes).into_any().unbind(),
+                BufferKind::Array => array_type(py)?.call1((typecode, PyBytes::new(py, &bytes)))?.unbind(),
+                BufferKind::MemoryView => PyMemoryView::from(PyBytes::new(py, &bytes).as_any())?
+                    .call_method1("cast", (typecode,))?
+                    .unbind(),
+                // 子进程中可以导入 numpy 而当前进程不能时退回 array.array
+                BufferKind::Numpy => match py.import("numpy") {
+                    Ok(numpy) => numpy.call_method1("frombuffer", (PyByteArray::new(py, &bytes), typecode))?.unbind(),
+                    Err(_) => array_type(py)?.call1((typecode, PyBytes::new(py, &bytes)))?.unbind(),
+                },
+            },
+        })
+    }
+
+    fn items_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Vec<Self>> {
+        obj.try_iter()?
+            .map(|item| Self::from_py(&item?))
+            .collect()
+    }
+
+    // array.array、memoryview 与 numpy.ndarray 保留容器与元素类型；其余格式的 memoryview 按 bytes 传递
+    fn buffer_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Option<Self>> {
+        let (kind, typecode) = if obj.is_instance(array_type(obj.py())?)? {
+            (BufferKind::Array, obj.getattr("typecode")?.extract::<String>()?)
+        } else if obj.is_instance_of::<PyMemoryView>() {
+            let format = obj.getattr("format")?.extract::<String>()?;
+            let ndim = obj.getattr("ndim")?.extract::<usize>()?;
+            if ndim > 1 || format.len() != 1 || !BUFFER_TYPECODES.contains(format.as_str()) {
+                return Ok(Some(Self::Bytes(obj.call_method0("tobytes")?.extract()?)));
+            }
+            (BufferKind::MemoryView, format)
+        } else if obj.get_type().fully_qualified_name()?.to_str()? == "numpy.ndarray" {
+            (BufferKind::Numpy, obj.getattr("dtype")?.getattr("char")?.extract::<String>()?)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(Self::Buffer(kind, typecode, obj.call_method0("tobytes")?.extract()?)))
+    }
+
+    pub fn str(value: impl Into<String>) -> Self {
+        Self::Str(value.into())
+    }
+
+    pub fn as_str(&self) -> PyResult<&str> {
+        match self {
+            Self::Str(value) => Ok(value),
+            _ => Err(PyValueError::new_err("Expected a string")),
+        }
+    }
+
+    // None 表示缺省的可选字符串
+    pub fn as_opt_str(&self) -> PyResult<Option<&str>> {
+        match self {
+            Self::None => Ok(None),
+            value => value.as_str().map(Some),
+        }
+    }
+}
+
+// 帧：u32 小端长度 + 编码后的值
+pub fn write_frame(writer: &mut impl Write, value: &Value) -> io::Result<()> {
+    let mut payload = Vec::new();
+    value.encode(&mut payload);
+    let len = u32::try_from(payload.len()).map_err(|_| invalid("Frame is too large".to_string()))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+pub fn read_frame(reader: &mut impl Read) -> io::Result<Value> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut payload)?;
+    let mut input = payload.as_slice();
+    let value = Value::decode(&mut input)?;
+    if !input.is_empty() {
+        return Err(invalid("Trailing bytes in frame".to_string()));
+    }
+    Ok(value)
+}
+
+fn items_into_py(py: Python<'_>, items: Vec<Value>) -> PyResult<Vec<PyObject>> {
+    items.into_iter().map(|item| item.into_py(py)).collect()
+}
+
+fn encode_items(out: &mut Vec<u8>, tag: u8, items: &[Value]) {
+    out.push(tag);
+    write_varint(out, items.len() as u64);
+    for item in items {
+        item.encode(out);
+    }
+}
+
+fn encode_entries(out: &mut Vec<u8>, tag: u8, entries: &[(Value, Value)]) {
+    out.push(tag);
+    write_varint(out, entries.len() as u64);
+    for (key, value) in entries {
+        key.encode(out);
+        value.encode(out);
+    }
+}
+
+fn decode_items(input: &mut &[u8]) -> io::Result<Vec<Value>> {
+    let len = read_varint(input)? as usize;
+    let mut items = Vec::with_capacity(len.min(input.len()));
+    for _ in 0..len {
+        items.push(Value::decode(input)?);
+    }
+    Ok(items)
+}
+
+fn decode_entries(input: &mut &[u8]) -> io::Result<Vec<(Value, Value)>> {
+    let len = read_varint(input)? as usize;
+    let mut entries = Vec::with_capacity(len.min(input.len()));
+    for _ in 0..len {
+        entries.push((Value::decode(input)?, Value::decode(input)?));
+    }
+    Ok(entries)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn read_byte(input: &mut &[u8]) -> io::Result<u8> {
+    Ok(take(input, 1)?[0])
+}
+
+fn read_varint(input: &mut &[u8]) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(input)?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid("Varint is too long".to_string()))
+}
+
+fn read_f64(input: &mut &[u8]) -> io::Result<f64> {
+    Ok(f64::from_le_bytes(take(input, 8)?.try_into().unwrap()))
+}
+
+fn read_string(input: &mut &[u8]) -> io::Result<String> {
+    let len = read_varint(input)? as usize;
+    String::from_utf8(take(input, len)?.to_vec()).map_err(|e| invalid(e.to_string()))
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if input.len() < len {
+        return Err(invalid("Unexpected end of frame".to_string()));
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
@@ -0,0 +1,9 @@
+# 针对已安装的扩展模块运行：pip install -e .[test]（或 maturin develop）后执行 pytest
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+@pytest.fixture
+def runtime():
+    return JsRuntime()
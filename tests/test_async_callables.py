@@ -0,0 +1,67 @@
+import asyncio
+import concurrent.futures
+
+import pytest
+
+CODE = """
+async function total(values) {
+    let sum = 0;
+    for (const v of values) sum += await double(v);
+    return sum;
+}
+function returnsPromise() { return double(1) instanceof Promise; }
+async function caught() {
+    try { await fail(); } catch (e) { return e.message; }
+}
+async function update() {
+    const config = await loadConfig();
+    config.loaded = true;
+    return config.name;
+}
+async function wait(future) { return (await future) + 1; }
+"""
+
+
+async def double(value):
+    await asyncio.sleep(0)
+    return value * 2
+
+
+async def fail():
+    await asyncio.sleep(0)
+    raise ValueError("async failure")
+
+
+@pytest.fixture
+def ctx(runtime):
+    runtime.expose("double", double)
+    runtime.expose("fail", fail)
+    return runtime.compile_code(CODE)
+
+
+def test_async_function_returns_promise(ctx):
+    assert ctx.call_function("returnsPromise") is True
+    assert ctx.call_function("total", [1, 2, 3], await_promise=True) == 12
+
+
+def test_exception_rejects_promise(ctx):
+    assert "async failure" in ctx.call_function("caught", await_promise=True)
+
+
+def test_result_is_a_live_proxy(runtime):
+    config = {"name": "app"}
+
+    async def load_config():
+        return config
+
+    runtime.expose("loadConfig", load_config)
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("update", await_promise=True) == "app"
+    assert config == {"name": "app", "loaded": True}
+
+
+def test_concurrent_future_becomes_promise(ctx):
+    with concurrent.futures.ThreadPoolExecutor(max_workers=1) as pool:
+        future = pool.submit(lambda: 41)
+        assert ctx.call_function("wait", future, await_promise=True) == 42
+
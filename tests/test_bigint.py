@@ -0,0 +1,35 @@
+import pytest
+
+from py_js_runtime import JsRuntime
+
+DESCRIBE = "function describe(x) { return typeof x + ':' + x.toString(); }\nfunction echo(x) { return x; }\n"
+
+
+@pytest.mark.parametrize("code, expected", [
+    ("0n", 0),
+    ("2n ** 100n", 2 ** 100),
+    ("-(2n ** 70n) - 5n", -(2 ** 70) - 5),
+    ("BigInt.asUintN(64, -1n)", 2 ** 64 - 1),
+])
+def test_bigint_to_python_is_lossless(runtime, code, expected):
+    assert runtime.eval(code) == expected
+
+
+def test_large_python_ints_become_bigint(runtime):
+    ctx = runtime.compile_code(DESCRIBE)
+    assert ctx.call_function("describe", 2 ** 64 + 1) == "bigint:18446744073709551617"
+    assert ctx.call_function("describe", -(2 ** 80)) == "bigint:-1208925819614629174706176"
+    assert ctx.call_function("describe", 2 ** 53 - 1) == "number:9007199254740991"
+    assert ctx.call_function("echo", 2 ** 100) == 2 ** 100
+
+
+def test_int_overflow_raise_rejects_unsafe_ints():
+    ctx = JsRuntime(int_overflow="raise").compile_code(DESCRIBE)
+    assert ctx.call_function("describe", 2 ** 53 - 1) == "number:9007199254740991"
+    with pytest.raises(ValueError, match="outside the JS safe integer range"):
+        ctx.call_function("describe", 2 ** 53)
+
+
+def test_invalid_int_overflow():
+    with pytest.raises(ValueError, match="int_overflow"):
+        JsRuntime(int_overflow="wrap")
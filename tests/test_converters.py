@@ -0,0 +1,62 @@
+from decimal import Decimal
+
+import pytest
+
+CODE = """
+class Long {
+    constructor(low, high) { this.low = low; this.high = high; }
+}
+function big() { return new Long(1, 2); }
+function describe(x) { return typeof x + ":" + JSON.stringify(x); }
+"""
+
+
+def test_js_converter_with_python_callable(runtime):
+    runtime.register_js_converter("Long", to_py=lambda d: d["high"] * 2 ** 32 + d["low"])
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("big") == 2 * 2 ** 32 + 1
+
+
+def test_js_converter_with_js_function(runtime):
+    runtime.register_js_converter("Long", js="(v) => `${v.high}:${v.low}`")
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("big") == "2:1"
+
+
+def test_js_converter_chains_js_and_python(runtime):
+    runtime.register_js_converter("Long", js="(v) => [v.high, v.low]", to_py=tuple)
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("big") == (2, 1)
+
+
+def test_py_converter_with_python_callable(runtime):
+    runtime.register_py_converter(Decimal, to_js=str)
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("describe", Decimal("1.10")) == 'string:"1.10"'
+
+
+def test_py_converter_with_js_function(runtime):
+    runtime.register_py_converter(Decimal, to_js=str, js="(s) => ({ decimal: s })")
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("describe", Decimal("1.10")) == 'object:{"decimal":"1.10"}'
+
+
+def test_py_converter_matches_subclasses(runtime):
+    class Money(Decimal):
+        pass
+
+    runtime.register_py_converter(Decimal, to_js=lambda d: "money" if isinstance(d, Money) else str(d))
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("describe", Money("1")) == 'string:"money"'
+
+
+def test_converter_requires_an_implementation(runtime):
+    with pytest.raises(ValueError, match="Either to_py or js"):
+        runtime.register_js_converter("Long")
+    with pytest.raises(ValueError, match="Either to_js or js"):
+        runtime.register_py_converter(Decimal)
+
+
+def test_js_converter_must_be_a_function(runtime):
+    with pytest.raises(ValueError, match="Converter is not a function"):
+        runtime.register_js_converter("Long", js="42")
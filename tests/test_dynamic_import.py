@@ -0,0 +1,48 @@
+import pytest
+
+from py_js_runtime import JsPromise
+
+
+@pytest.fixture
+def dep_dir(tmp_path):
+    (tmp_path / "dep.js").write_text("export const value = 7;\nexport default 'dep';\n")
+    return tmp_path
+
+
+def test_eval_resolves_import_relative_to_filename(runtime, dep_dir):
+    result = runtime.eval("import('./dep.js').then((m) => m.value)", filename=str(dep_dir / "main.js"))
+    assert result == 7
+
+
+def test_compile_code_waits_for_top_level_import(runtime, dep_dir):
+    ctx = runtime.compile_code(
+        "var loaded = null;\n"
+        "import('./dep.js').then((m) => { loaded = m.default; });\n"
+        "function getLoaded() { return loaded; }\n",
+        filename=str(dep_dir / "script.js"),
+    )
+    assert ctx.call_function("getLoaded") == "dep"
+
+
+def test_import_inside_function_returns_promise_unless_awaited(runtime, dep_dir):
+    ctx = runtime.compile_code(
+        "async function load() { return (await import('./dep.js')).value; }",
+        filename=str(dep_dir / "script.js"),
+    )
+    promise = ctx.call_function("load")
+    assert isinstance(promise, JsPromise)
+    assert promise.result() == 7
+    assert ctx.call_function("load", await_promise=True) == 7
+
+
+def test_import_from_module(runtime, dep_dir):
+    ctx = runtime.compile_module(
+        "export async function load() { return (await import('./dep.js')).value; }",
+        base_dir=str(dep_dir),
+    )
+    assert ctx.call_function("load", await_promise=True) == 7
+
+
+def test_failed_import_raises(runtime, dep_dir):
+    with pytest.raises(RuntimeError, match="missing.js"):
+        runtime.eval("import('./missing.js')", filename=str(dep_dir / "main.js"))
@@ -0,0 +1,82 @@
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+class Counter:
+    def __init__(self):
+        self.count = 0
+        self._hidden = True
+
+    def increment(self, step=1):
+        self.count += step
+        return self.count
+
+
+class Broken:
+    @property
+    def value(self):
+        raise RuntimeError("property failed")
+
+
+def test_attributes_and_methods_are_live(runtime):
+    counter = Counter()
+    runtime.expose("counter", counter)
+    assert runtime.eval("counter.increment(2)") == 2
+    assert counter.count == 2
+    # Python 侧的修改对 JS 立即可见，JS 侧的赋值写回 Python 对象
+    counter.count = 10
+    assert runtime.eval("counter.count") == 10
+    runtime.eval("counter.count = 20")
+    assert counter.count == 20
+
+
+def test_in_and_enumeration(runtime):
+    runtime.expose("counter", Counter())
+    assert runtime.eval("'increment' in counter") is True
+    assert runtime.eval("'missing' in counter") is False
+    keys = runtime.eval("Object.keys(counter)")
+    assert "count" in keys
+    assert "_hidden" not in keys
+
+
+def test_mapping_proxy(runtime):
+    settings = {"theme": "dark", "nested": {"size": 1}}
+    runtime.expose("settings", settings)
+    runtime.eval("settings.theme = 'light'; settings.nested.size = 2; settings.added = true;")
+    assert settings == {"theme": "light", "nested": {"size": 2}, "added": True}
+    assert runtime.eval("Object.keys(settings)") == ["theme", "nested", "added"]
+    runtime.eval("delete settings.added")
+    assert "added" not in settings
+
+
+def test_sequence_proxy(runtime):
+    items = [1, 2, 3]
+    runtime.expose("items", items)
+    assert runtime.eval("items.length") == 3
+    assert runtime.eval("items[1]") == 2
+    assert runtime.eval("items[5]") is None
+    runtime.eval("items[0] = 10")
+    assert items == [10, 2, 3]
+
+
+def test_python_exception_is_thrown_in_js(runtime):
+    runtime.expose("broken", Broken())
+    message = runtime.eval("try { broken.value; } catch (e) { e.message }")
+    assert "property failed" in message
+    with pytest.raises(RuntimeError, match="property failed"):
+        runtime.eval("broken.value")
+
+
+def test_proxy_unwraps_to_same_object(runtime):
+    counter = Counter()
+    runtime.expose("counter", counter)
+    assert runtime.eval("counter") is counter
+    ctx = runtime.compile_code("function pick() { return { counter }; }")
+    assert ctx.call_function("pick")["counter"] is counter
+
+
+def test_expose_is_not_supported_in_process_mode():
+    runtime = JsRuntime(isolation="process")
+    with pytest.raises(NotImplementedError):
+        runtime.expose("counter", Counter())
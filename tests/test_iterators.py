@@ -0,0 +1,82 @@
+import asyncio
+
+from py_js_runtime import JsAsyncIterator, JsIterator
+
+CODE = """
+globalThis.produced = 0;
+function* naturals() { for (let i = 1; ; i++) { produced = i; yield i; } }
+function range(n) {
+    return { [Symbol.iterator]() { let i = 0; return { next: () => ({ done: i >= n, value: i++ }) }; } };
+}
+async function* ticks(n) { for (let i = 0; i < n; i++) { await null; yield i; } }
+function sum(iterable) { let total = 0; for (const v of iterable) total += v; return total; }
+function firstTwo(iterable) {
+    const out = [];
+    for (const v of iterable) { out.push(v); if (out.length === 2) break; }
+    return out;
+}
+async function collect(iterable) { const out = []; for await (const v of iterable) out.push(v); return out; }
+function same(it) { return it === globalThis.saved; }
+function save() { globalThis.saved = naturals(); return globalThis.saved; }
+"""
+
+
+def test_generator_is_lazy(runtime):
+    ctx = runtime.compile_code(CODE)
+    iterator = ctx.call_function("naturals")
+    assert isinstance(iterator, JsIterator)
+    assert next(iterator) == 1
+    assert next(iterator) == 2
+    assert runtime.eval("produced") == 2
+
+
+def test_custom_iterable(runtime):
+    ctx = runtime.compile_code(CODE)
+    assert list(ctx.call_function("range", 3)) == [0, 1, 2]
+
+
+def test_async_generator(runtime):
+    ctx = runtime.compile_code(CODE)
+    iterator = ctx.call_function("ticks", 3)
+    assert isinstance(iterator, JsAsyncIterator)
+
+    async def main():
+        return [value async for value in iterator]
+
+    assert asyncio.run(main()) == [0, 1, 2]
+
+
+def test_python_generator_in_js(runtime):
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("sum", (i * i for i in range(4))) == 14
+
+
+def test_early_exit_closes_generator(runtime):
+    ctx = runtime.compile_code(CODE)
+    closed = []
+
+    def numbers():
+        try:
+            yield from range(10)
+        finally:
+            closed.append(True)
+
+    assert ctx.call_function("firstTwo", numbers()) == [0, 1]
+    assert closed == [True]
+
+
+def test_python_async_generator_in_js(runtime):
+    ctx = runtime.compile_code(CODE)
+
+    async def letters():
+        for letter in "abc":
+            await asyncio.sleep(0)
+            yield letter
+
+    assert ctx.call_function("collect", letters(), await_promise=True) == ["a", "b", "c"]
+
+
+def test_js_iterator_round_trip(runtime):
+    ctx = runtime.compile_code(CODE)
+    iterator = ctx.call_function("save")
+    assert ctx.call_function("same", iterator) is True
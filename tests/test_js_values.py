@@ -0,0 +1,67 @@
+import re
+
+import pytest
+
+from py_js_runtime import JsException, JsRuntime, JsSymbol
+
+VALUES = """
+function regexp() { return /(?<word>ab+)c/i; }
+function error() {
+    const e = new RangeError("too big", { cause: "limit" });
+    e.code = "E_RANGE";
+    return e;
+}
+function symbol() { return Symbol("tag"); }
+function describeSymbol(s) { return typeof s + ":" + s.description; }
+function boxed() { return [new Number(1.5), new String("s"), new Boolean(false)]; }
+function proxy() { return new Proxy({ a: 1 }, { get: () => 99 }); }
+class Rect {
+    constructor(w, h) { this.w = w; this.h = h; }
+    get area() { return this.w * this.h; }
+}
+function rect() { return new Rect(2, 3); }
+"""
+
+
+@pytest.fixture
+def ctx(runtime):
+    return runtime.compile_code(VALUES)
+
+
+def test_regexp_becomes_pattern(ctx):
+    pattern = ctx.call_function("regexp")
+    assert isinstance(pattern, re.Pattern)
+    assert pattern.flags & re.IGNORECASE
+    assert pattern.match("ABBC").group("word") == "ABB"
+
+
+def test_error_becomes_exception_object(ctx):
+    error = ctx.call_function("error")
+    assert isinstance(error, JsException)
+    assert error.name == "RangeError"
+    assert error.message == "too big"
+    assert error.cause == "limit"
+    assert error.code == "E_RANGE"
+    assert "too big" in error.stack
+
+
+def test_symbol_round_trip(ctx):
+    symbol = ctx.call_function("symbol")
+    assert isinstance(symbol, JsSymbol)
+    assert repr(symbol) == "Symbol(tag)"
+    assert ctx.call_function("describeSymbol", symbol) == "symbol:tag"
+    assert ctx.call_function("describeSymbol", JsSymbol("py")) == "symbol:py"
+
+
+def test_boxed_primitives_are_unwrapped(ctx):
+    assert ctx.call_function("boxed") == [1.5, "s", False]
+
+
+def test_proxy_converts_through_target(ctx):
+    assert ctx.call_function("proxy") == {"a": 1}
+
+
+def test_prototype_getters_are_opt_in(ctx):
+    assert ctx.call_function("rect") == {"w": 2, "h": 3}
+    ctx = JsRuntime(prototype_getters=True).compile_code(VALUES)
+    assert ctx.call_function("rect") == {"w": 2, "h": 3, "area": 6}
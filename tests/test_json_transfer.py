@@ -0,0 +1,60 @@
+import json
+
+import pytest
+
+CODE = """
+function echo(x) { return x; }
+function sum(items) { return items.reduce((total, item) => total + item.value, 0); }
+function special() { return { date: new Date(0), map: new Map([["a", 1]]), missing: undefined, list: [undefined] }; }
+function big() { return 1n; }
+async function later() { return { done: true }; }
+"""
+
+PAYLOAD = {"name": "x", "items": [{"value": 1}, {"value": 2.5}], "flag": True, "none": None}
+
+
+@pytest.fixture
+def ctx(runtime):
+    return runtime.compile_code(CODE)
+
+
+def test_json_transfer_round_trip(ctx):
+    assert ctx.call_function("echo", PAYLOAD, transfer="json") == PAYLOAD
+    assert ctx.call_function("sum", PAYLOAD["items"], transfer="json") == 3.5
+
+
+def test_call_function_json_returns_raw_string(ctx):
+    result = ctx.call_function_json("echo", PAYLOAD)
+    assert isinstance(result, str)
+    assert json.loads(result) == PAYLOAD
+    assert ctx.call_function_json("echo", None) == "null"
+
+
+def test_json_semantics(ctx):
+    # 按 JSON.stringify 的规则：Date 转为 ISO 字符串，Map 为空对象，undefined 属性被省略
+    assert ctx.call_function("special", transfer="json") == {
+        "date": "1970-01-01T00:00:00.000Z",
+        "map": {},
+        "list": [None],
+    }
+
+
+def test_promise_requires_await(ctx):
+    with pytest.raises(RuntimeError, match="returned a Promise; pass await_promise=True"):
+        ctx.call_function_json("later")
+    with pytest.raises(RuntimeError, match="returned a Promise; pass await_promise=True"):
+        ctx.call_function("later", transfer="json")
+    assert ctx.call_function_json("later", await_promise=True) == '{"done":true}'
+    assert ctx.call_function("later", transfer="json", await_promise=True) == {"done": True}
+
+
+def test_non_json_values_are_rejected(ctx):
+    with pytest.raises(ValueError, match="Serialization error"):
+        ctx.call_function("echo", {1, 2}, transfer="json")
+    with pytest.raises(RuntimeError, match="JSON error"):
+        ctx.call_function_json("big")
+
+
+def test_invalid_transfer(ctx):
+    with pytest.raises(ValueError, match="Invalid transfer"):
+        ctx.call_function("echo", 1, transfer="pickle")
@@ -0,0 +1,61 @@
+import pytest
+
+from py_js_runtime import JsMap, JsRuntime
+
+MAPS = """
+function strings() { return new Map([["a", 1], ["b", 2]]); }
+function mixed() { return new Map([[[1, 2], "array"], [3, "number"]]); }
+function describe(x) { return x instanceof Map ? "Map:" + x.size + ":" + x.get("a") : typeof x; }
+function arrayKey(map) { return Array.from(map.keys()).filter(Array.isArray).length; }
+"""
+
+
+def test_dict_mode_is_default(runtime):
+    ctx = runtime.compile_code(MAPS)
+    assert ctx.call_function("strings") == {"a": 1, "b": 2}
+
+
+def test_dict_mode_rejects_unhashable_keys(runtime):
+    ctx = runtime.compile_code(MAPS)
+    with pytest.raises(TypeError):
+        ctx.call_function("mixed")
+
+
+def test_jsmap_mode_keeps_any_keys():
+    ctx = JsRuntime(map_mode="jsmap").compile_code(MAPS)
+    result = ctx.call_function("mixed")
+    assert isinstance(result, JsMap)
+    assert len(result) == 2
+    assert result[[1, 2]] == "array"
+    assert result[3] == "number"
+    assert result.items() == [([1, 2], "array"), (3, "number")]
+    # 传回 JS 后仍是 Map，数组键保持不变
+    assert ctx.call_function("arrayKey", result) == 1
+
+
+def test_pairs_mode():
+    ctx = JsRuntime(map_mode="pairs").compile_code(MAPS)
+    assert ctx.call_function("strings") == [("a", 1), ("b", 2)]
+
+
+def test_dict_sent_as_map_through_jsmap(runtime):
+    ctx = runtime.compile_code(MAPS)
+    assert ctx.call_function("describe", JsMap({"a": 1, "b": 2})) == "Map:2:1"
+    assert ctx.call_function("describe", {"a": 1}) == "object"
+
+
+def test_jsmap_behaves_like_a_mapping():
+    mapping = JsMap([("a", 1)])
+    mapping["b"] = 2
+    assert "b" in mapping
+    assert mapping.get("c", 3) == 3
+    del mapping["a"]
+    assert list(mapping) == ["b"]
+    assert mapping.to_dict() == {"b": 2}
+    with pytest.raises(KeyError):
+        mapping["a"]
+
+
+def test_invalid_map_mode():
+    with pytest.raises(ValueError, match="map_mode"):
+        JsRuntime(map_mode="list")
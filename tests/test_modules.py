@@ -0,0 +1,47 @@
+import pytest
+
+
+def test_compile_module_keeps_all_exports(runtime):
+    ctx = runtime.compile_module("""
+export const VERSION = "1.2.0";
+export const LIMITS = { max: 10 };
+export class Counter {}
+export function add(a, b) { return a + b; }
+export default function twice(x) { return x * 2; }
+""")
+    assert ctx.get_property("VERSION") == "1.2.0"
+    assert ctx.get_property("LIMITS") == {"max": 10}
+    assert ctx.get_property("Counter") is not None
+    assert ctx.call_function("add", 1, 2) == 3
+    assert ctx.call_function("default", 21) == 42
+
+
+def test_compile_module_resolves_relative_imports_against_base_dir(runtime, tmp_path):
+    (tmp_path / "lib").mkdir()
+    (tmp_path / "lib" / "math.js").write_text("export const square = (x) => x * x;\n")
+    ctx = runtime.compile_module(
+        "import { square } from './lib/math.js';\nexport const nine = square(3);\n",
+        base_dir=str(tmp_path),
+    )
+    assert ctx.get_property("nine") == 9
+
+
+def test_compile_module_with_explicit_specifier(runtime, tmp_path):
+    (tmp_path / "dep.js").write_text("export const name = 'dep';\n")
+    ctx = runtime.compile_module(
+        "import { name } from './dep.js';\nexport function describe() { return name + ':' + import.meta.url.split('/').pop(); }\n",
+        specifier="entry.js",
+        base_dir=str(tmp_path),
+    )
+    assert ctx.call_function("describe") == "dep:entry.js"
+
+
+def test_compile_module_reports_evaluation_errors(runtime):
+    with pytest.raises(RuntimeError, match="module failed"):
+        runtime.compile_module("throw new Error('module failed');")
+
+
+def test_missing_export_raises_key_error(runtime):
+    ctx = runtime.compile_module("export const a = 1;")
+    with pytest.raises(KeyError):
+        ctx.get_property("b")
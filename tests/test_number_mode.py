@@ -0,0 +1,39 @@
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+def test_auto_mode_keeps_safe_integral_numbers_as_int(runtime):
+    assert runtime.eval("1.5") == 1.5
+    value = runtime.eval("3")
+    assert value == 3 and type(value) is int
+    # 超出安全整数范围的整数值可能已被舍入，保持为 float
+    assert type(runtime.eval("1e20")) is float
+
+
+def test_float_mode_always_returns_float():
+    value = JsRuntime(number_mode="float").eval("3")
+    assert value == 3.0 and type(value) is float
+
+
+def test_int32_mode_returns_int_only_for_int32_values():
+    runtime = JsRuntime(number_mode="int32")
+    assert type(runtime.eval("3")) is int
+    assert type(runtime.eval("2 ** 40")) is float
+
+
+def test_unsafe_integral_number_raises_with_int_overflow_raise():
+    with pytest.raises(ValueError, match="may have lost precision"):
+        JsRuntime(int_overflow="raise").eval("2 ** 60")
+
+
+def test_float_mode_rejects_ints_that_lose_precision():
+    ctx = JsRuntime(number_mode="float").compile_code("function kind(x) { return typeof x; }")
+    assert ctx.call_function("kind", 2 ** 60) == "number"
+    with pytest.raises(ValueError, match="cannot be represented exactly"):
+        ctx.call_function("kind", 2 ** 60 + 1)
+
+
+def test_invalid_number_mode():
+    with pytest.raises(ValueError, match="number_mode"):
+        JsRuntime(number_mode="decimal")
@@ -0,0 +1,83 @@
+import asyncio
+
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+def add(a, b):
+    return a + b
+
+
+def fail():
+    raise ValueError("bad input")
+
+
+def keys(options):
+    return sorted(options)
+
+
+async def fetch(key):
+    await asyncio.sleep(0)
+    return {"key": key}
+
+
+def deferred():
+    # 普通函数返回的协程同样转为 Promise
+    return fetch("deferred")
+
+
+OPS = {"add": add, "fail": fail, "keys": keys, "fetch": fetch, "deferred": deferred}
+
+CODE = """
+function viaCore(a, b) { return Deno.core.ops.add(a, b); }
+function viaNamespace(a, b) { return host.add(a, b); }
+function caught() {
+    try { host.fail(); } catch (e) { return e.message; }
+}
+function optionKeys() { return host.keys({ b: 1, a: 2 }); }
+async function fetched(key) { return (await host.fetch(key)).key; }
+function isPromise() { return host.fetch("x") instanceof Promise; }
+async function deferredKey() {
+    const pending = host.deferred();
+    return [pending instanceof Promise, (await pending).key];
+}
+"""
+
+
+@pytest.fixture
+def ctx():
+    return JsRuntime(ops=OPS).compile_code(CODE)
+
+
+def test_ops_are_mounted_on_core_and_namespace(ctx):
+    assert ctx.call_function("viaCore", 1, 2) == 3
+    assert ctx.call_function("viaNamespace", "a", "b") == "ab"
+
+
+def test_custom_namespace():
+    runtime = JsRuntime(ops={"add": add}, ops_namespace="py")
+    assert runtime.eval("py.add(2, 3)") == 5
+    assert runtime.eval("typeof globalThis.host") == "undefined"
+
+
+def test_op_arguments_are_converted(ctx):
+    assert ctx.call_function("optionKeys") == ["a", "b"]
+
+
+def test_python_exception_is_thrown_in_js(ctx):
+    assert "bad input" in ctx.call_function("caught")
+
+
+def test_async_op_returns_promise(ctx):
+    assert ctx.call_function("isPromise") is True
+    assert ctx.call_function("fetched", "k", await_promise=True) == "k"
+
+
+def test_sync_op_returning_coroutine_returns_promise(ctx):
+    assert ctx.call_function("deferredKey", await_promise=True) == [True, "deferred"]
+
+
+def test_ops_are_not_supported_in_process_mode():
+    with pytest.raises(ValueError, match="not supported"):
+        JsRuntime(ops=OPS, isolation="process")
@@ -0,0 +1,230 @@
+import array
+import enum
+import os
+import signal
+import uuid
+from dataclasses import dataclass
+from datetime import date, datetime, time, timezone
+from decimal import Decimal
+
+import pytest
+
+from py_js_runtime import JsException, JsMap, JsRuntime, JsWorkerError
+
+CODE = """
+class Long { constructor(low, high) { this.low = low; this.high = high; } }
+function add(a, b) { return a + b; }
+function fail() { throw new Error("boom"); }
+function big() { return new Long(1, 2); }
+function symbol() { return Symbol("s"); }
+function detailed() {
+    const error = new TypeError("bad value", { cause: new Error("root") });
+    error.code = "E_BAD";
+    throw error;
+}
+"""
+
+
+@pytest.fixture
+def runtime():
+    return JsRuntime(isolation="process")
+
+
+def worker_pids():
+    # 子进程以 sys.executable -c 启动，命令行中包含 _serve_worker
+    pids = set()
+    for entry in os.listdir("/proc"):
+        if not entry.isdigit():
+            continue
+        try:
+            with open(f"/proc/{entry}/stat", "rb") as f:
+                stat = f.read()
+            with open(f"/proc/{entry}/cmdline", "rb") as f:
+                cmdline = f.read()
+        except OSError:
+            continue
+        ppid = int(stat.rsplit(b")", 1)[1].split()[1])
+        if ppid == os.getpid() and b"_serve_worker" in cmdline:
+            pids.add(int(entry))
+    return pids
+
+
+def test_eval_and_calls(runtime):
+    assert runtime.eval("1 + 2") == 3
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("add", 2, 3) == 5
+    assert ctx.call_function("add", "a", "b", transfer="json") == "ab"
+    assert ctx.call_function_json("add", 1, 1) == "2"
+    assert runtime.compile_module("export const value = 42;").get_property("value") == 42
+
+
+def test_js_error_keeps_worker_alive(runtime):
+    ctx = runtime.compile_code(CODE)
+    with pytest.raises(RuntimeError, match="boom"):
+        ctx.call_function("fail")
+    assert ctx.call_function("add", 1, 1) == 2
+
+
+def test_remote_errors_keep_type_and_attributes(runtime):
+    ctx = runtime.compile_code(CODE)
+    with pytest.raises(JsException) as info:
+        ctx.call_function("detailed")
+    error = info.value
+    assert error.name == "TypeError"
+    assert error.message == "bad value"
+    assert error.code == "E_BAD"
+    assert "detailed" in error.stack
+    assert isinstance(error.cause, JsException)
+    assert error.cause.message == "root"
+    # 内置异常按原类型与参数还原
+    with pytest.raises(KeyError) as info:
+        ctx.get_property("missing")
+    assert info.value.args == ("Property missing not found",)
+    with pytest.raises(ValueError, match="Invalid transfer"):
+        ctx.call_function("add", 1, 2, transfer="pickle")
+
+
+def test_worker_timeout_kills_and_restarts():
+    runtime = JsRuntime(isolation="process", worker_timeout=0.5)
+    with pytest.raises(JsWorkerError, match="did not respond within"):
+        runtime.eval("while (true) {}")
+    assert runtime.eval("1 + 1") == 2
+
+
+def test_invalid_worker_timeout():
+    with pytest.raises(ValueError, match="worker_timeout"):
+        JsRuntime(isolation="process", worker_timeout=0)
+
+
+def test_js_output_does_not_corrupt_protocol(runtime):
+    assert runtime.eval("Deno.core.print('noise on stdout\\n'); 'ok'") == "ok"
+
+
+def test_wire_types(runtime):
+    value = runtime.eval("({ n: null, b: true, i: 7, big: 2n ** 70n, f: 1.5, s: 'x', list: [1, [2]], nested: { k: 'v' } })")
+    assert value == {"n": None, "b": True, "i": 7, "big": 2 ** 70, "f": 1.5, "s": "x", "list": [1, [2]], "nested": {"k": "v"}}
+    ctx = runtime.compile_code("function echo(x) { return x; }")
+    assert ctx.call_function("echo", b"\x00\x01") == b"\x00\x01"
+    assert ctx.call_function("echo", (1, 2)) == [1, 2]
+
+
+class Color(enum.Enum):
+    RED = "red"
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+TYPES = """
+function echo(x) { return x; }
+function kind(x) { return Object.prototype.toString.call(x); }
+function results() {
+    return {
+        set: new Set([1, 2]),
+        date: new Date(86400000),
+        ints: new Int16Array([1, -2]),
+        floats: new Float64Array([0.5]),
+    };
+}
+"""
+
+
+# 两种后端对同一输入应得到相同的结果
+@pytest.mark.parametrize("isolation", ["thread", "process"])
+def test_wire_types_match_thread_backend(isolation):
+    ctx = JsRuntime(isolation=isolation).compile_code(TYPES)
+    assert ctx.call_function("results") == {
+        "set": {1, 2},
+        "date": datetime.fromtimestamp(86400),
+        "ints": array.array("h", [1, -2]),
+        "floats": array.array("d", [0.5]),
+    }
+    assert ctx.call_function("kind", {1, 2}) == "[object Set]"
+    assert ctx.call_function("kind", frozenset([1])) == "[object Set]"
+    assert ctx.call_function("kind", bytearray(b"ab")) == "[object Uint8Array]"
+    assert ctx.call_function("kind", array.array("i", [1])) == "[object Int32Array]"
+    assert ctx.call_function("kind", memoryview(array.array("f", [1.0]))) == "[object Float32Array]"
+    assert ctx.call_function("kind", JsMap([(1, "a")])) == "[object Map]"
+    assert ctx.call_function("echo", datetime(2020, 1, 2, 3, 4, 5, tzinfo=timezone.utc)) == datetime.fromtimestamp(1577934245)
+    assert ctx.call_function("echo", date(1970, 1, 2)) == datetime.fromtimestamp(86400)
+    assert ctx.call_function("echo", time(12, 30)) == "12:30:00"
+    assert ctx.call_function("echo", Decimal("1.5")) == 1.5
+    assert ctx.call_function("echo", Decimal("0.1")) == "0.1"
+    assert ctx.call_function("echo", uuid.UUID(int=1)) == "00000000-0000-0000-0000-000000000001"
+    assert ctx.call_function("echo", Color.RED) == "red"
+    assert ctx.call_function("echo", Point(1, 2)) == {"x": 1, "y": 2}
+
+
+@pytest.mark.parametrize("isolation", ["thread", "process"])
+def test_map_modes_match_thread_backend(isolation):
+    runtime = JsRuntime(isolation=isolation, map_mode="jsmap")
+    value = runtime.eval("new Map([[1, 'a'], ['k', [2]]])")
+    assert isinstance(value, JsMap)
+    assert value.items() == [(1, "a"), ("k", [2])]
+    runtime = JsRuntime(isolation=isolation, map_mode="pairs")
+    assert runtime.eval("new Map([[1, 'a']])") == [(1, "a")]
+
+
+@pytest.mark.parametrize("isolation", ["thread", "process"])
+def test_typed_array_modes_match_thread_backend(isolation):
+    runtime = JsRuntime(isolation=isolation, typed_array="memoryview")
+    value = runtime.eval("new Uint16Array([1, 2])")
+    assert isinstance(value, memoryview)
+    assert value.format == "H"
+    assert value.tolist() == [1, 2]
+
+
+def test_untransferable_values(runtime):
+    ctx = runtime.compile_code(CODE)
+    with pytest.raises(TypeError, match="process boundary"):
+        ctx.call_function("symbol")
+    with pytest.raises(TypeError, match="process boundary"):
+        ctx.call_function("add", object(), 1)
+    with pytest.raises(TypeError, match="process boundary"):
+        runtime.eval("/a+/")
+
+
+def test_unsupported_features(runtime):
+    with pytest.raises(NotImplementedError):
+        runtime.expose("x", {})
+    with pytest.raises(NotImplementedError):
+        runtime.register_python_module("m", {})
+    with pytest.raises(NotImplementedError):
+        runtime.register_class(dict)
+    with pytest.raises(NotImplementedError):
+        runtime.register_py_converter(int, to_js=str)
+    with pytest.raises(NotImplementedError):
+        runtime.register_js_converter("Long", to_py=str)
+    with pytest.raises(ValueError):
+        JsRuntime(isolation="process", module_loader=lambda path: None)
+
+
+@pytest.mark.skipif(not os.path.isdir("/proc"), reason="needs /proc to find the worker process")
+def test_worker_crash_and_restart():
+    before = worker_pids()
+    runtime = JsRuntime(isolation="process")
+    runtime.register_js_converter("Long", js="(v) => `${v.high}:${v.low}`")
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("big") == "2:1"
+    (pid,) = worker_pids() - before
+
+    os.kill(pid, signal.SIGKILL)
+    with pytest.raises(JsWorkerError, match="exited unexpectedly"):
+        runtime.eval("1")
+    # 下一次调用自动重启子进程
+    assert runtime.eval("1 + 1") == 2
+    assert pid not in worker_pids()
+    # 旧进程中编译的上下文已失效，需重新编译
+    with pytest.raises(JsWorkerError, match="has been restarted"):
+        ctx.call_function("add", 1, 2)
+    ctx = runtime.compile_code(CODE)
+    assert ctx.call_function("add", 1, 2) == 3
+    # 注册的 JS 转换器在重启后重放
+    assert ctx.call_function("big") == "2:1"
+
+
+def test_worker_error_is_a_runtime_error():
+    assert issubclass(JsWorkerError, RuntimeError)
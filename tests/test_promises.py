@@ -0,0 +1,76 @@
+import asyncio
+
+import pytest
+
+from py_js_runtime import JsException, JsPromise
+
+CODE = """
+async function later(value) { await null; return value; }
+function nested() { return { id: 1, data: later("payload") }; }
+function chained() { return later(1).then((v) => v + 1); }
+function rejected() { return Promise.reject(new TypeError("nope")); }
+function never() { return new Promise(() => {}); }
+function keep(p) { globalThis.kept = p; return true; }
+function isKept(p) { return p === globalThis.kept; }
+async function plusOne(future) { return (await future) + 1; }
+"""
+
+
+@pytest.fixture
+def ctx(runtime):
+    return runtime.compile_code(CODE)
+
+
+def test_returned_promise_is_js_promise(ctx):
+    promise = ctx.call_function("later", 5)
+    assert isinstance(promise, JsPromise)
+    assert promise.result() == 5
+    assert promise.done()
+
+
+def test_nested_promise_in_result(ctx):
+    result = ctx.call_function("nested")
+    assert result["id"] == 1
+    assert isinstance(result["data"], JsPromise)
+    assert result["data"].result() == "payload"
+
+
+def test_promise_can_be_awaited_in_asyncio(ctx):
+    async def main():
+        return await ctx.call_function("chained")
+
+    assert asyncio.run(main()) == 2
+
+
+def test_rejection_raises_js_exception(ctx):
+    promise = ctx.call_function("rejected")
+    with pytest.raises(JsException, match="nope") as info:
+        promise.result()
+    assert info.value.name == "TypeError"
+
+    async def main():
+        await ctx.call_function("rejected")
+
+    with pytest.raises(JsException, match="nope"):
+        asyncio.run(main())
+
+
+def test_never_settling_promise(ctx):
+    with pytest.raises(RuntimeError):
+        ctx.call_function("never").result()
+
+
+def test_promise_round_trip(ctx):
+    promise = ctx.call_function("later", 1)
+    assert ctx.call_function("keep", promise) is True
+    assert ctx.call_function("isKept", promise) is True
+
+
+def test_asyncio_future_becomes_promise(ctx):
+    async def main():
+        future = asyncio.get_running_loop().create_future()
+        promise = ctx.call_function("plusOne", future)
+        future.set_result(41)
+        return await promise
+
+    assert asyncio.run(main()) == 42
@@ -0,0 +1,96 @@
+import datetime
+import enum
+import uuid
+from dataclasses import dataclass
+from decimal import Decimal
+from typing import NamedTuple
+
+import pytest
+
+DESCRIBE = """
+function tag(x) { return Object.prototype.toString.call(x); }
+function items(x) { return Array.from(x); }
+function echo(x) { return x; }
+function time(x) { return x instanceof Date ? x.toISOString() : x; }
+"""
+
+
+class Color(enum.Enum):
+    RED = "red"
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+class Pair(NamedTuple):
+    left: str
+    right: str
+
+
+class Model:
+    def model_dump(self):
+        return {"kind": "model"}
+
+
+class Custom:
+    def __js__(self):
+        return ["custom", 1]
+
+
+class SelfReturning:
+    def __js__(self):
+        return self
+
+
+@pytest.fixture
+def ctx(runtime):
+    return runtime.compile_code(DESCRIBE)
+
+
+@pytest.mark.parametrize("value, expected", [
+    ((1, 2), "[object Array]"),
+    ({1, 2}, "[object Set]"),
+    (frozenset({1}), "[object Set]"),
+    (bytearray(b"ab"), "[object Uint8Array]"),
+    (memoryview(b"ab"), "[object Uint8Array]"),
+    (datetime.date(2024, 1, 2), "[object Date]"),
+])
+def test_container_types(ctx, value, expected):
+    assert ctx.call_function("tag", value) == expected
+
+
+def test_set_contents(ctx):
+    assert sorted(ctx.call_function("items", {3, 1, 2})) == [1, 2, 3]
+
+
+def test_date_and_time(ctx):
+    assert ctx.call_function("time", datetime.date(2024, 1, 2)) == "2024-01-02T00:00:00.000Z"
+    assert ctx.call_function("time", datetime.time(12, 30)) == "12:30:00"
+
+
+def test_value_types(ctx):
+    assert ctx.call_function("echo", Color.RED) == "red"
+    assert ctx.call_function("echo", uuid.UUID(int=1)) == "00000000-0000-0000-0000-000000000001"
+    assert ctx.call_function("echo", Point(1, 2)) == {"x": 1, "y": 2}
+    assert ctx.call_function("echo", Pair("a", "b")) == {"left": "a", "right": "b"}
+    assert ctx.call_function("echo", Model()) == {"kind": "model"}
+    assert ctx.call_function("echo", Custom()) == ["custom", 1]
+
+
+def test_decimal_keeps_precision(ctx):
+    assert ctx.call_function("echo", Decimal("1.5")) == 1.5
+    # 无法用 double 精确表示的 Decimal 以字符串传递
+    assert ctx.call_function("echo", Decimal("0.1")) == "0.1"
+
+
+def test_js_protocol_returning_self_is_rejected(ctx):
+    with pytest.raises(ValueError, match="returned itself"):
+        ctx.call_function("echo", SelfReturning())
+
+
+def test_unsupported_type(ctx):
+    with pytest.raises(ValueError, match="Unsupported Python type"):
+        ctx.call_function("echo", object())
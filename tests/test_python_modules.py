@@ -0,0 +1,63 @@
+import asyncio
+import types
+
+import pytest
+
+
+def make_module():
+    module = types.ModuleType("helpers")
+    module.VERSION = "1.0"
+    module.double = lambda x: x * 2
+
+    async def slow_upper(text):
+        await asyncio.sleep(0)
+        return text.upper()
+
+    module.slow_upper = slow_upper
+    return module
+
+
+def test_import_functions_and_constants(runtime):
+    runtime.register_python_module("helpers", make_module())
+    ctx = runtime.compile_module(
+        "import { double, VERSION } from 'python:helpers';\n"
+        "export const doubled = double(21);\n"
+        "export const version = VERSION;\n"
+    )
+    assert ctx.get_property("doubled") == 42
+    assert ctx.get_property("version") == "1.0"
+
+
+def test_dict_module_and_default_export(runtime):
+    runtime.register_python_module("config", {"name": "app", "greet": lambda who: "hi " + who})
+    ctx = runtime.compile_module(
+        "import config from 'python:config';\n"
+        "export const text = config.greet(config.name);\n"
+    )
+    assert ctx.get_property("text") == "hi app"
+
+
+def test_async_export_returns_promise(runtime):
+    runtime.register_python_module("helpers", make_module())
+    ctx = runtime.compile_module(
+        "import { slow_upper } from 'python:helpers';\n"
+        "export const upper = await slow_upper('abc');\n"
+    )
+    assert ctx.get_property("upper") == "ABC"
+
+
+def test_python_exception_is_thrown_in_js(runtime):
+    def explode():
+        raise KeyError("missing")
+
+    runtime.register_python_module("broken", {"explode": explode})
+    ctx = runtime.compile_module(
+        "import { explode } from 'python:broken';\n"
+        "export function run() { try { explode(); } catch (e) { return e.message; } }\n"
+    )
+    assert "missing" in ctx.call_function("run")
+
+
+def test_unregistered_module(runtime):
+    with pytest.raises(RuntimeError, match="Python module not registered"):
+        runtime.compile_module("import { x } from 'python:nowhere';\n")
@@ -0,0 +1,77 @@
+from concurrent.futures import ThreadPoolExecutor
+
+import pytest
+
+from py_js_runtime import JsRuntime
+
+CODE = """
+function recurse(n) { return n === 0 ? 0 : 1 + pyRecurse(n - 1); }
+function viaPython(x) { return pyCall(x); }
+function square(x) { return x * x; }
+async function later(value) { await null; return value; }
+async function now(value) { return value; }
+function nestedWait(name) {
+    try { return pyWait(name); } catch (e) { return "error: " + e.message; }
+}
+function nestedResult() {
+    try { return pyResult(later(1)); } catch (e) { return "error: " + e.message; }
+}
+"""
+
+
+def make_context(runtime):
+    holder = {}
+
+    def recurse(n):
+        return holder["ctx"].call_function("recurse", n)
+
+    def wait(name):
+        return holder["ctx"].call_function(name, 7, await_promise=True)
+
+    runtime.expose("pyRecurse", recurse)
+    runtime.expose("pyCall", lambda x: holder["ctx"].call_function("square", x))
+    runtime.expose("pyWait", wait)
+    runtime.expose("pyResult", lambda promise: promise.result())
+    holder["ctx"] = runtime.compile_code(CODE)
+    return holder["ctx"]
+
+
+def test_nested_call_function(runtime):
+    ctx = make_context(runtime)
+    assert ctx.call_function("viaPython", 6) == 36
+    assert ctx.call_function("recurse", 5) == 5
+
+
+def test_nested_eval(runtime):
+    runtime.expose("pyEval", lambda code: runtime.eval(code))
+    assert runtime.eval("pyEval('20 + 1') * 2") == 42
+
+
+def test_nesting_depth_exceeded():
+    runtime = JsRuntime(max_nesting_depth=4)
+    ctx = make_context(runtime)
+    assert ctx.call_function("recurse", 2) == 2
+    with pytest.raises(RuntimeError, match="Maximum nesting depth of 4 exceeded"):
+        ctx.call_function("recurse", 10)
+    # 超出深度后 runtime 仍可正常使用
+    assert ctx.call_function("recurse", 2) == 2
+    assert runtime.eval("1 + 1") == 2
+
+
+def test_pending_promise_in_nested_call(runtime):
+    ctx = make_context(runtime)
+    assert "Cannot wait for a pending Promise inside a nested call" in ctx.call_function("nestedWait", "later")
+    # 已兑现的 Promise 在嵌套调用中可以直接取回结果
+    assert ctx.call_function("nestedWait", "now") == 7
+
+
+def test_pending_js_promise_result_in_nested_call(runtime):
+    ctx = make_context(runtime)
+    assert "Cannot wait for a pending Promise inside a nested call" in ctx.call_function("nestedResult")
+
+
+def test_concurrent_calls_are_serialized(runtime):
+    ctx = make_context(runtime)
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        results = list(pool.map(lambda x: ctx.call_function("viaPython", x), range(20)))
+    assert results == [x * x for x in range(20)]
@@ -0,0 +1,99 @@
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def scaled(self, factor):
+        return Point(self.x * factor, self.y * factor)
+
+    def total(self):
+        return self.x + self.y
+
+
+class Animal:
+    def __init__(self, name):
+        self.name = name
+
+    def speak(self):
+        return self.name + " makes a sound"
+
+
+class Dog(Animal):
+    def speak(self):
+        return self.name + " barks"
+
+
+def test_class_is_constructor(runtime):
+    runtime.register_class(Point)
+    assert runtime.eval("const p = new Point(1, 2); p.total()") == 3
+    assert runtime.eval("new Point(1, 2).x") == 1
+    assert runtime.eval("new Point(1, 2) instanceof Point") is True
+    # 方法返回的实例同样是 Point 的代理
+    assert runtime.eval("new Point(1, 2).scaled(3) instanceof Point") is True
+
+
+def test_constructed_instance_unwraps_to_python(runtime):
+    runtime.register_class(Point)
+    point = runtime.eval("const q = new Point(3, 4); q.x = 30; q")
+    assert isinstance(point, Point)
+    assert (point.x, point.y) == (30, 4)
+
+
+def test_subclass_instanceof(runtime):
+    runtime.register_class(Animal)
+    runtime.register_class(Dog)
+    assert runtime.eval("new Dog('rex') instanceof Animal") is True
+    assert runtime.eval("new Dog('rex').speak()") == "rex barks"
+    assert runtime.eval("new Animal('cat') instanceof Dog") is False
+
+
+def test_subclass_registered_before_base(runtime):
+    runtime.register_class(Dog)
+    runtime.register_class(Animal)
+    assert runtime.eval("new Dog('rex') instanceof Animal") is True
+    assert runtime.eval("new Animal('cat') instanceof Animal") is True
+
+
+def test_instance_wrapped_before_base_is_registered(runtime):
+    runtime.expose("adopt", lambda name: Dog(name))
+    runtime.register_class(Dog)
+    runtime.eval("globalThis.early = adopt('rex')")
+    runtime.register_class(Animal)
+    assert runtime.eval("early instanceof Animal") is True
+    assert runtime.eval("adopt('max') instanceof Dog") is True
+
+
+def test_instances_created_in_python_keep_their_class(runtime):
+    runtime.register_class(Animal)
+    runtime.expose("adopt", lambda name: Dog(name))
+    assert runtime.eval("adopt('rex') instanceof Animal") is True
+
+
+def test_custom_name(runtime):
+    runtime.register_class(Point, name="Vec")
+    assert runtime.eval("new Vec(1, 1).total()") == 2
+    assert runtime.eval("typeof Point") == "undefined"
+
+
+def test_calling_without_new_is_a_type_error(runtime):
+    runtime.register_class(Point)
+    assert runtime.eval("try { Point(1, 2); } catch (e) { e instanceof TypeError }") is True
+    with pytest.raises(RuntimeError, match="cannot be invoked without 'new'"):
+        runtime.eval("Point(1, 2)")
+
+
+def test_constructor_errors_are_thrown_in_js(runtime):
+    runtime.register_class(Point)
+    message = runtime.eval("try { new Point(1); } catch (e) { e.message }")
+    assert "missing 1 required positional argument" in message
+
+
+def test_register_class_is_not_supported_in_process_mode():
+    runtime = JsRuntime(isolation="process")
+    with pytest.raises(NotImplementedError):
+        runtime.register_class(Point)
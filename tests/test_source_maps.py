@@ -0,0 +1,52 @@
+import base64
+import json
+import re
+
+import pytest
+
+# 压缩后的单行脚本，抛出异常的位置映射到 orig.js 第 10 行
+MINIFIED = 'function explode(){throw new Error("boom")}'
+SOURCE_MAP = {
+    "version": 3,
+    "sources": ["file:///src/app/orig.js"],
+    "names": [],
+    "mappings": "AASA",
+}
+
+
+def error_message(ctx):
+    with pytest.raises(RuntimeError) as excinfo:
+        ctx.call_function("explode")
+    return str(excinfo.value)
+
+
+def test_inline_source_map_rewrites_stack(runtime, tmp_path):
+    payload = base64.b64encode(json.dumps(SOURCE_MAP).encode()).decode()
+    code = MINIFIED + "\n//# sourceMappingURL=data:application/json;base64," + payload + "\n"
+    ctx = runtime.compile_code(code, filename=str(tmp_path / "bundle.min.js"))
+    message = error_message(ctx)
+    assert re.search(r"orig\.js:10:\d+", message), message
+
+
+def test_external_source_map_rewrites_stack(runtime, tmp_path):
+    (tmp_path / "bundle.min.js.map").write_text(json.dumps(SOURCE_MAP))
+    code = MINIFIED + "\n//# sourceMappingURL=bundle.min.js.map\n"
+    ctx = runtime.compile_code(code, filename=str(tmp_path / "bundle.min.js"))
+    message = error_message(ctx)
+    assert re.search(r"orig\.js:10:\d+", message), message
+
+
+def test_source_map_applies_to_modules_loaded_from_disk(runtime, tmp_path):
+    payload = base64.b64encode(json.dumps(SOURCE_MAP).encode()).decode()
+    (tmp_path / "bundle.min.js").write_text(
+        "export " + MINIFIED + "\n//# sourceMappingURL=data:application/json;base64," + payload + "\n"
+    )
+    ctx = runtime.compile_file(str(tmp_path / "bundle.min.js"))
+    message = error_message(ctx)
+    assert re.search(r"orig\.js:10:\d+", message), message
+
+
+def test_scripts_without_source_map_keep_generated_positions(runtime, tmp_path):
+    ctx = runtime.compile_code(MINIFIED, filename=str(tmp_path / "plain.js"))
+    message = error_message(ctx)
+    assert re.search(r"plain\.js:1:\d+", message), message
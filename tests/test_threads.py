@@ -0,0 +1,101 @@
+import gc
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor
+
+import pytest
+
+from py_js_runtime import JsRuntime
+
+CODE = """
+function add(a, b) { return a + b; }
+async function later(value) { await null; return value; }
+async function spin(ms) {
+    await null;
+    const end = Date.now() + ms;
+    while (Date.now() < end) {}
+    return "done";
+}
+function busy(ms) {
+    const end = Date.now() + ms;
+    while (Date.now() < end) {}
+    return "done";
+}
+"""
+
+
+def test_runtime_created_in_another_thread():
+    with ThreadPoolExecutor(max_workers=1) as pool:
+        runtime = pool.submit(JsRuntime).result()
+        ctx = pool.submit(runtime.compile_code, CODE).result()
+    assert ctx.call_function("add", 1, 2) == 3
+    assert runtime.eval("typeof add") == "function"
+
+
+def test_calls_from_many_threads(runtime):
+    ctx = runtime.compile_code(CODE)
+    with ThreadPoolExecutor(max_workers=8) as pool:
+        results = list(pool.map(lambda i: ctx.call_function("add", i, i), range(50)))
+    assert results == [i * 2 for i in range(50)]
+
+
+def test_promise_result_from_another_thread(runtime):
+    ctx = runtime.compile_code(CODE)
+    promise = ctx.call_function("later", "value")
+    with ThreadPoolExecutor(max_workers=1) as pool:
+        assert pool.submit(promise.result).result() == "value"
+
+
+def test_gil_is_released_while_waiting(runtime):
+    ctx = runtime.compile_code(CODE)
+    finished = threading.Event()
+
+    def call():
+        ctx.call_function("spin", 1000, await_promise=True)
+        finished.set()
+
+    worker = threading.Thread(target=call)
+    worker.start()
+    time.sleep(0.2)
+    # JS 仍在运行时，其他 Python 线程可以立即取得 GIL
+    start = time.monotonic()
+    sum(range(1000))
+    elapsed = time.monotonic() - start
+    still_running = not finished.is_set()
+    worker.join()
+    assert still_running
+    assert elapsed < 0.5
+
+
+@pytest.mark.parametrize("call", [
+    lambda runtime, ctx: ctx.call_function("busy", 1000),
+    lambda runtime, ctx: runtime.eval("busy(1000)"),
+])
+def test_gil_is_released_while_js_runs(runtime, call):
+    ctx = runtime.compile_code(CODE)
+    finished = threading.Event()
+
+    def run():
+        call(runtime, ctx)
+        finished.set()
+
+    worker = threading.Thread(target=run)
+    worker.start()
+    time.sleep(0.2)
+    # 同步的 CPU 密集 JS 运行期间（无事件循环），其他 Python 线程仍可取得 GIL
+    start = time.monotonic()
+    sum(range(1000))
+    elapsed = time.monotonic() - start
+    still_running = not finished.is_set()
+    worker.join()
+    assert still_running
+    assert elapsed < 0.5
+
+
+def test_promise_outliving_runtime():
+    runtime = JsRuntime()
+    promise = runtime.eval("({ p: new Promise(() => {}) })")["p"]
+    del runtime
+    gc.collect()
+    with pytest.raises(RuntimeError, match="JsRuntime has been dropped"):
+        promise.result()
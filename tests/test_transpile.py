@@ -0,0 +1,68 @@
+import re
+
+import pytest
+
+from py_js_runtime import JsRuntime
+
+TS_SOURCE = """interface Point {
+  x: number;
+  y: number;
+}
+
+type Mode = "a" | "b";
+
+function fail(mode: Mode): never {
+  throw new Error("failed in " + mode);
+}
+
+function norm(p: Point): number {
+  return Math.sqrt(p.x * p.x + p.y * p.y);
+}
+"""
+
+
+def test_compile_code_transpiles_typescript(runtime):
+    ctx = runtime.compile_code(TS_SOURCE, filename="calc.ts")
+    assert ctx.call_function("norm", {"x": 3, "y": 4}) == 5
+
+
+def test_typescript_errors_point_to_original_lines(runtime):
+    ctx = runtime.compile_code(TS_SOURCE, filename="calc.ts")
+    with pytest.raises(RuntimeError) as excinfo:
+        ctx.call_function("fail", "a")
+    # throw 位于 TS 源码第 9 行，类型声明被擦除后行号仍应指向原文件
+    assert re.search(r"calc\.ts:9:\d+", str(excinfo.value)), str(excinfo.value)
+
+
+def test_cached_transpile_keeps_each_filename(runtime):
+    runtime.compile_code(TS_SOURCE, filename="first.ts")
+    # 相同源码命中缓存，source map 仍应指向第二个文件
+    ctx = runtime.compile_code(TS_SOURCE, filename="second.ts")
+    with pytest.raises(RuntimeError) as excinfo:
+        ctx.call_function("fail", "b")
+    assert re.search(r"second\.ts:9:\d+", str(excinfo.value)), str(excinfo.value)
+
+
+def test_module_loader_transpiles_typescript_imports():
+    runtime = JsRuntime(modules={
+        "util.ts": "export const twice = (n: number): number => n * 2;\n",
+    })
+    ctx = runtime.compile_module("import { twice } from 'util.ts';\nexport const result = twice(4);\n")
+    assert ctx.get_property("result") == 8
+
+
+def test_compile_code_transpiles_jsx(runtime):
+    ctx = runtime.compile_code(
+        "const React = { createElement: (tag, props, ...children) => ({ tag, props, children }) };\n"
+        "function render(name) { return <div id=\"greeting\">hi {name}</div>; }\n",
+        filename="view.jsx",
+    )
+    element = ctx.call_function("render", "bob")
+    assert element["tag"] == "div"
+    assert element["props"] == {"id": "greeting"}
+    assert "bob" in element["children"]
+
+
+def test_transpile_errors_are_reported(runtime):
+    with pytest.raises(RuntimeError, match="Failed to transpile"):
+        runtime.compile_code("let x: = ;", filename="broken.ts")
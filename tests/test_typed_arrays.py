@@ -0,0 +1,53 @@
+from array import array
+
+import pytest
+
+from py_js_runtime import JsRuntime
+
+DESCRIBE = "function describe(x) { return x.constructor.name + ':' + Array.from(x).join(','); }"
+
+
+def test_auto_mode_keeps_element_types(runtime):
+    floats = runtime.eval("new Float32Array([1.5, 2.5])")
+    assert isinstance(floats, array) and floats.typecode == "f"
+    assert floats.tolist() == [1.5, 2.5]
+    assert runtime.eval("new Int32Array([1, -2])") == array("i", [1, -2])
+    # Uint8Array 默认仍为 bytes
+    assert runtime.eval("new Uint8Array([1, 2, 3])") == b"\x01\x02\x03"
+
+
+def test_array_mode_converts_uint8_too():
+    assert JsRuntime(typed_array="array").eval("new Uint8Array([1, 2])") == array("B", [1, 2])
+
+
+def test_memoryview_mode():
+    view = JsRuntime(typed_array="memoryview").eval("new Int16Array([-1, 300])")
+    assert isinstance(view, memoryview)
+    assert view.format == "h"
+    assert view.tolist() == [-1, 300]
+
+
+def test_bytes_mode():
+    data = JsRuntime(typed_array="bytes").eval("new Float64Array([1, 2])")
+    assert isinstance(data, bytes) and len(data) == 16
+
+
+def test_numpy_mode():
+    numpy = pytest.importorskip("numpy")
+    result = JsRuntime(typed_array="numpy").eval("new Float32Array([0.5, 1.5])")
+    assert isinstance(result, numpy.ndarray)
+    assert result.dtype == numpy.float32
+    assert result.tolist() == [0.5, 1.5]
+
+
+def test_python_buffers_become_matching_typed_arrays(runtime):
+    ctx = runtime.compile_code(DESCRIBE)
+    assert ctx.call_function("describe", array("d", [1.5, 2.5])) == "Float64Array:1.5,2.5"
+    assert ctx.call_function("describe", memoryview(array("i", [7, -7]))) == "Int32Array:7,-7"
+    assert ctx.call_function("describe", bytearray(b"\x01\x02")) == "Uint8Array:1,2"
+
+
+def test_numpy_arrays_become_typed_arrays(runtime):
+    numpy = pytest.importorskip("numpy")
+    ctx = runtime.compile_code(DESCRIBE)
+    assert ctx.call_function("describe", numpy.array([1, -1], dtype=numpy.int16)) == "Int16Array:1,-1"
@@ -0,0 +1,57 @@
+import pytest
+
+from py_js_runtime import JsRuntime
+
+
+def test_import_between_virtual_files():
+    runtime = JsRuntime(modules={
+        "lib/math.js": "export const double = (x) => x * 2;\n",
+        "main.js": "import { double } from './lib/math.js';\nexport const answer = double(21);\n",
+    })
+    ctx = runtime.compile_module("import { answer } from 'main.js';\nexport const result = answer;\n")
+    assert ctx.get_property("result") == 42
+
+
+def test_require_between_virtual_files():
+    runtime = JsRuntime(modules={
+        "a.js": "const b = require('./b.js');\nmodule.exports = { total: b.value + 1 };\n",
+        "b.js": "module.exports = { value: 1 };\n",
+    })
+    ctx = runtime.compile_code("function total() { return require('a.js').total; }")
+    assert ctx.call_function("total") == 2
+
+
+def test_module_loader_receives_full_specifier():
+    requested = []
+
+    def loader(specifier):
+        requested.append(specifier)
+        if specifier == "virtual:///greet.js":
+            return "export const greet = (name) => 'hi ' + name;\n"
+        if specifier == "virtual:///count.js":
+            return "module.exports = 3;\n"
+        return None
+
+    runtime = JsRuntime(module_loader=loader)
+    ctx = runtime.compile_module("import { greet } from 'greet.js';\nexport const text = greet('bob');\n")
+    assert ctx.get_property("text") == "hi bob"
+    # import 与 require 传给回调的都是完整的 specifier
+    ctx = runtime.compile_code("function count() { return require('count.js'); }")
+    assert ctx.call_function("count") == 3
+    assert "virtual:///greet.js" in requested
+    assert "virtual:///count.js" in requested
+
+
+def test_virtual_file_shadows_file_on_disk(tmp_path, monkeypatch):
+    (tmp_path / "dep.js").write_text("export default 'disk';\n")
+    monkeypatch.chdir(tmp_path)
+    runtime = JsRuntime(modules={"dep.js": "export default 'virtual';\n"})
+    ctx = runtime.compile_module("import dep from './dep.js';\nexport const value = dep;\n")
+    assert ctx.get_property("value") == "virtual"
+
+
+def test_missing_virtual_module_does_not_fall_back_to_disk():
+    runtime = JsRuntime(modules={"present.js": "module.exports = 1;\n"})
+    ctx = runtime.compile_code("function load() { return require('missing.js'); }")
+    with pytest.raises(RuntimeError, match="Module not found"):
+        ctx.call_function("load")
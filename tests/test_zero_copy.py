@@ -0,0 +1,87 @@
+import gc
+from array import array
+
+import pytest
+
+from py_js_runtime import JsBuffer, JsRuntime
+
+SHARED = """
+globalThis.shared = new Uint8Array(4);
+function get() { return shared; }
+function poke(i, v) { shared[i] = v; }
+function peek(i) { return shared[i]; }
+function fill(target, v) { target.fill(v); return target.length; }
+function keep(target) { globalThis.kept = target; }
+"""
+
+
+@pytest.fixture
+def ctx():
+    return JsRuntime(zero_copy=True).compile_code(SHARED)
+
+
+def test_typed_array_is_shared_without_copy(ctx):
+    buffer = ctx.call_function("get")
+    assert isinstance(buffer, JsBuffer)
+    view = memoryview(buffer)
+    ctx.call_function("poke", 1, 9)
+    assert view[1] == 9
+    view[2] = 5
+    assert ctx.call_function("peek", 2) == 5
+
+
+def test_typed_array_format_and_length():
+    runtime = JsRuntime(zero_copy=True)
+    view = memoryview(runtime.eval("new Float32Array([1.5, 2.5])"))
+    assert view.format == "f"
+    assert view.tolist() == [1.5, 2.5]
+    assert len(runtime.eval("new ArrayBuffer(8)")) == 8
+
+
+def test_python_buffers_are_shared_with_js(ctx):
+    data = bytearray(4)
+    assert ctx.call_function("fill", data, 7) == 4
+    assert data == bytearray(b"\x07" * 4)
+
+    numbers = array("i", [1, 2, 3])
+    ctx.call_function("fill", numbers, -1)
+    assert numbers.tolist() == [-1, -1, -1]
+
+
+def test_read_only_buffers_are_copied(ctx):
+    data = b"\x00\x00"
+    assert ctx.call_function("fill", data, 1) == 2
+    assert data == b"\x00\x00"
+    assert ctx.call_function("fill", memoryview(bytearray(2)).toreadonly(), 1) == 2
+
+
+def test_js_buffer_is_writable_from_python():
+    buffer = JsRuntime(zero_copy=True).eval("new Uint8Array(2)")
+    view = memoryview(buffer)
+    assert not view.readonly
+    view[0] = 1
+    assert bytes(view) == b"\x01\x00"
+
+
+def test_buffer_stays_exported_while_js_holds_it(ctx):
+    data = bytearray(4)
+    ctx.call_function("keep", data)
+    # ArrayBuffer 仍引用这段内存，bytearray 不能改变大小
+    with pytest.raises(BufferError):
+        data.append(1)
+
+
+def test_js_buffer_outlives_runtime():
+    runtime = JsRuntime(zero_copy=True)
+    buffer = runtime.eval("new Uint8Array([1, 2, 3])")
+    del runtime
+    gc.collect()
+    assert bytes(memoryview(buffer)) == b"\x01\x02\x03"
+
+
+def test_copies_by_default(runtime):
+    ctx = runtime.compile_code(SHARED)
+    data = bytearray(2)
+    ctx.call_function("fill", data, 1)
+    assert data == bytearray(2)
+    assert isinstance(ctx.call_function("get"), bytes)
@@ -0,0 +1,83 @@
+// 通过 rlib 直接测试 Rust 调用方使用的类型化接口，不经过 Python
+use py_js_runtime::types::error::JsError;
+use py_js_runtime::{ConvertOptions, JsEngine};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn engine() -> JsEngine {
+    JsEngine::with_options(None, None, ConvertOptions::default(), None, 16).unwrap()
+}
+
+// 定义全局函数；脚本以 undefined 结尾，结果可反序列化为 ()
+fn define(engine: &JsEngine, code: &str) {
+    engine.eval_as::<()>(&format!("{}\nundefined", code)).unwrap();
+}
+
+#[test]
+fn eval_as_deserializes_result() {
+    let engine = engine();
+    assert_eq!(engine.eval_as::<i32>("1 + 2").unwrap(), 3);
+    assert_eq!(engine.eval_as::<String>("'a' + 'b'").unwrap(), "ab");
+    assert_eq!(engine.eval_as::<Vec<f64>>("[0.5, 1.5]").unwrap(), vec![0.5, 1.5]);
+}
+
+#[test]
+fn eval_as_waits_for_promise() {
+    let engine = engine();
+    let value: i32 = engine.eval_as("Promise.resolve(20).then((v) => v + 1)").unwrap();
+    assert_eq!(value, 21);
+}
+
+#[test]
+fn call_with_tuple_arguments() {
+    let engine = engine();
+    define(&engine, "globalThis.add = (a, b) => a + b; globalThis.answer = () => 42;");
+    assert_eq!(engine.call::<_, i32>("add", (1, 2)).unwrap(), 3);
+    assert_eq!(engine.call::<_, i32>("answer", ()).unwrap(), 42);
+}
+
+#[test]
+fn call_round_trips_structs() {
+    let engine = engine();
+    define(&engine, "globalThis.flip = (p) => ({ x: p.y, y: p.x });");
+    let point: Point = engine.call("flip", (Point { x: 1, y: 2 },)).unwrap();
+    assert_eq!(point, Point { x: 2, y: 1 });
+}
+
+#[test]
+fn call_resolves_nested_names_with_receiver() {
+    let engine = engine();
+    define(&engine, "globalThis.math = { factor: 2, double(v) { return v * this.factor; } };");
+    assert_eq!(engine.call::<_, i32>("math.double", (4,)).unwrap(), 8);
+}
+
+#[test]
+fn call_reports_missing_functions() {
+    let engine = engine();
+    define(&engine, "globalThis.value = 1;");
+    let err = engine.call::<_, ()>("missing.fn", ()).unwrap_err();
+    assert!(matches!(&err, JsError::RuntimeError(msg) if msg.contains("is not defined")), "{}", err);
+    let err = engine.call::<_, ()>("value", ()).unwrap_err();
+    assert!(matches!(&err, JsError::RuntimeError(msg) if msg.contains("is not a function")), "{}", err);
+}
+
+#[test]
+fn js_exceptions_become_errors() {
+    let engine = engine();
+    define(&engine, "globalThis.fail = () => { throw new Error('boom'); };");
+    let err = engine.call::<_, ()>("fail", ()).unwrap_err();
+    assert!(matches!(&err, JsError::ExecutionError(msg) if msg.contains("boom")), "{}", err);
+    assert!(engine.eval_as::<i32>("Promise.reject(new Error('later'))").unwrap_err().to_string().contains("later"));
+}
+
+#[test]
+fn mismatched_types_are_conversion_errors() {
+    let engine = engine();
+    let err = engine.eval_as::<i32>("'text'").unwrap_err();
+    assert!(matches!(err, JsError::ConversionError(_)), "{}", err);
+}